@@ -23,7 +23,7 @@ impl Map {
         }
     }
 
-    pub(crate) fn consume(&self) -> Result<Ref<InnerMap>, MapError> {
+    pub(crate) fn consume(&self) -> Result<Ref<'_, InnerMap>, MapError> {
         if self.consumed.replace(true) {
             Err(MapError::AlreadyConsumed)
         } else {
@@ -31,7 +31,7 @@ impl Map {
         }
     }
 
-    pub(crate) fn borrow_mut(&self) -> Result<RefMut<InnerMap>, MapError> {
+    pub(crate) fn borrow_mut(&self) -> Result<RefMut<'_, InnerMap>, MapError> {
         if self.consumed.take() {
             Err(MapError::AlreadyConsumed)
         } else {
@@ -1,6 +1,7 @@
 use std::borrow::Borrow;
 use std::error::Error;
 use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 #[inline]
@@ -8,6 +9,38 @@ pub fn identity<T>(t: T) -> T {
     t
 }
 
+static PROGRESS_DONE: AtomicU64 = AtomicU64::new(0);
+static PROGRESS_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// Reports the progress of a long running solution, `cargo aoc` shows it next to the elapsed time.
+///
+/// ```
+/// for i in 0..1000 {
+///     aoc_runner::progress(i, 1000);
+///     // ...
+/// }
+/// ```
+#[inline]
+pub fn progress(done: u64, total: u64) {
+    PROGRESS_DONE.store(done, Ordering::Relaxed);
+    PROGRESS_TOTAL.store(total, Ordering::Relaxed);
+}
+
+/// Gets the last progress reported by the running solution, if any
+pub fn current_progress() -> Option<(u64, u64)> {
+    let total = PROGRESS_TOTAL.load(Ordering::Relaxed);
+    if total == 0 {
+        None
+    } else {
+        Some((PROGRESS_DONE.load(Ordering::Relaxed), total))
+    }
+}
+
+/// Forgets the reported progress, before running another solution
+pub fn reset_progress() {
+    progress(0, 0);
+}
+
 #[derive(Clone, Debug)]
 pub struct ArcStr(Arc<str>);
 
@@ -0,0 +1,76 @@
+use crate::config::Config;
+use crate::results::{PartResult, Status};
+use aoc_runner_internal::{Day, Part};
+use std::collections::BTreeMap;
+use std::error;
+use std::fs;
+use std::path::PathBuf;
+
+/// The known answers of a year, stored in `{account_dir}/{year}/answers.json`.
+///
+/// Answers are stored next to the inputs, since they depend on them.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<u8, BTreeMap<u8, String>>,
+}
+
+impl Answers {
+    fn path(year: i32) -> PathBuf {
+        PathBuf::from(format!(
+            "{}/{}/answers.json",
+            Config::get().account_dir(),
+            year
+        ))
+    }
+
+    /// Loads the answers of a year, an empty store is returned if there is no answers file
+    pub fn load(year: i32) -> Result<Self, Box<dyn error::Error>> {
+        let path = Answers::path(year);
+
+        let answers = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&str> {
+        self.answers
+            .get(&day.0)
+            .and_then(|parts| parts.get(&part.0))
+            .map(String::as_str)
+    }
+
+    /// Stores the answer of a part, replacing the previous one
+    pub fn insert(&mut self, day: Day, part: Part, answer: &str) {
+        self.answers
+            .entry(day.0)
+            .or_default()
+            .insert(part.0, answer.to_string());
+    }
+
+    /// Writes the answers of a year to its answers file
+    pub fn save(&self, year: i32) -> Result<(), Box<dyn error::Error>> {
+        let path = Answers::path(year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&self.answers)?)?;
+        Ok(())
+    }
+
+    pub fn contains_day(&self, day: Day) -> bool {
+        self.answers.contains_key(&day.0)
+    }
+
+    /// Tells if a result failed, or gave another answer than the stored one
+    pub fn is_failure(&self, result: &PartResult) -> bool {
+        result.status != Status::Ok
+            || self
+                .get(result.day, result.part)
+                .is_some_and(|answer| answer != result.answer)
+    }
+}
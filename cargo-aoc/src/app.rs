@@ -1,31 +1,110 @@
 use crate::{
-    credentials::CredentialsManager, date, project::ProjectManager, Bench, Credentials, Input,
+    answers::Answers,
+    badge,
+    bench_backend::{self, BenchBackend, Templates},
+    bench_env,
+    bench_history,
+    browser,
+    build_profile::BuildProfile,
+    calendar,
+    checksums::{self, Checksums, InputState},
+    clipboard,
+    color,
+    compression,
+    config::Config,
+    credentials::{self, CredentialsManager, SESSION_ENV},
+    criterion::{self, Estimates},
+    date,
+    doctor::{self, Check},
+    encryption,
+    errors::NotUnlocked,
+    examples,
+    features,
+    http::{self, Endpoint},
+    memory,
+    notify,
+    output::{self, BenchRecord, OutputFormat, Record},
+    picker,
+    progress,
+    project::ProjectManager,
+    puzzle,
+    redact,
+    report,
+    results::{self, PartResult, Status},
+    self_update,
+    shared_cache,
+    stack_size,
+    stars,
+    submissions::{self, Submission},
+    submit::{self, Verdict},
+    time_format,
+    titles,
+    tui::{self, Command, Dashboard, RawMode},
+    workspace,
+    worktree::Worktree,
+    Answer, Badge, Bench, Calendar, Clean, Credentials, Export, Init, Input, New, Open, Puzzle,
+    Report, SelfUpdate, Stars, Submissions, Submit, Test, Today, Tui, Verify, Watch,
 };
-use aoc_runner_internal::{Day, Part};
+use aoc_runner_internal::{Day, DayPart, DayParts, Part};
 use date::AOCDate;
-use reqwest::{
-    header::{HeaderMap, COOKIE, USER_AGENT},
-    StatusCode,
-};
-use std::io::Write;
-use std::path::Path;
+use reqwest::StatusCode;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::env;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process;
-use std::{error, sync::Arc};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::error;
 use std::{
     error::Error,
     fs::{self, File},
 };
 
-use crate::Cli;
+use crate::Run;
+
+/// The exit code used when a part fails, or gives another answer than the stored one
+pub const WRONG_ANSWER_EXIT_CODE: i32 = 2;
+
+/// The environment variable giving the generated runner the file to write its results to
+const RESULTS_ENV: &str = "CARGO_AOC_RESULTS";
+
+/// The exit code used when the solutions are slower than the runtime budget
+pub const OVER_BUDGET_EXIT_CODE: i32 = 3;
 
-const CARGO_AOC_USER_AGENT: &str = "github.com/gobanos/cargo-aoc by gregory.obanos@gmail.com";
+/// The number of inputs downloaded at once by `input --all`
+const INPUT_JOBS: usize = 4;
+
+/// The number of times the input is requested while the puzzle isn't unlocked yet, with `--wait`
+const UNLOCK_ATTEMPTS: u32 = 8;
 
 pub fn execute_credentials(args: &Credentials) {
     let mut creds_manager = CredentialsManager::new();
 
-    if let Some(new_session) = &args.set {
+    if args.check {
+        check_credentials(&creds_manager);
+        return;
+    }
+
+    let from_browser = if args.from_browser {
+        match browser::session_cookie() {
+            Ok(cookie) => Some(cookie),
+            Err(e) => {
+                println!("Error reading the session cookie: {}", e);
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    let new_session = args.set.as_ref().or(from_browser.as_ref());
+    if let Some(new_session) = new_session {
         // Tries to set the session token
-        match creds_manager.set_session_token(new_session.to_owned()) {
+        match creds_manager.set_session_token(new_session.to_owned(), args.keyring) {
             Ok(()) => println!("Credentials sucessfully changed!"),
             Err(e) => println!("Error changing credentials: {}", e),
         }
@@ -33,81 +112,150 @@ pub fn execute_credentials(args: &Credentials) {
 
     // Displays the stored session token
     match creds_manager.get_session_token() {
+        Ok(cred) if new_session.is_none() && env::var_os(SESSION_ENV).is_some() => {
+            println!("Current credentials (from {}): {}", SESSION_ENV, cred)
+        }
         Ok(cred) => println!("Current credentials: {}", cred),
         Err(e) => println!("Error: {}", e),
     }
 }
 
+/// Checks the stored session token with a request of the home page, which shows the account
+/// only to logged in users
+fn check_credentials(creds_manager: &CredentialsManager) {
+    let profile = CredentialsManager::keyring_account();
+    let last_verified = credentials::last_verified();
+    println!("Profile: {}", profile);
+
+    let token = match creds_manager.get_session_token() {
+        Ok(token) => token,
+        Err(e) => {
+            println!("Error: {}, set it using `cargo aoc credentials {{token}}`", e);
+            return;
+        }
+    };
+    if let Some(source) = creds_manager.token_source() {
+        println!("Token from: {}", source);
+    }
+    match last_verified {
+        Some(time) => println!("Last verified: {}", time.format("%Y-%m-%d %H:%M")),
+        None => println!("Last verified: never"),
+    }
+
+    match http::get(&Endpoint::PAGE, &http::url("/"), Some(&token)) {
+        Ok(response) if response.body.contains("[Log Out]") => {
+            let name = submit::account_name(&response.body);
+            println!("Account: {}", name.as_deref().unwrap_or("unknown"));
+            println!("The session token is valid");
+            if let Err(e) = credentials::set_verified() {
+                eprintln!("Could not store the verification time: {}", e);
+            }
+        }
+        Ok(_) => println!("{}", credentials::session_expired_error()),
+        Err(e) => println!("Could not check the session token: {}", e),
+    }
+}
+
 /// Executes the "input" subcommand of the app
 pub fn execute_input(args: &Input) -> Result<(), Box<dyn Error>> {
-    // Gets the token or exit if it's not referenced.
-    let token = CredentialsManager::new().get_session_token().expect(
+    if args.check {
+        let dates: Vec<AOCDate> = if args.all {
+            let year = args
+                .year
+                .expect("Need to specify a year to run cargo-aoc input --all");
+            let now = chrono::Utc::now();
+            (1..=calendar::days(year))
+                .filter(|&day| calendar::unlock_time(year, day) <= now)
+                .map(|day| AOCDate { day, year })
+                .collect()
+        } else {
+            vec![AOCDate::new(args)]
+        };
+        return check_inputs(&dates);
+    }
+
+    if args.redact {
+        let date = AOCDate::new(args);
+        if args.force {
+            fetch_input(date)?;
+        } else {
+            download_input(date)?;
+        }
+        print!("{}", redact::redact(&fs::read_to_string(date.filename())?));
+        return Ok(());
+    }
+
+    // Exits if the token is not referenced.
+    CredentialsManager::new().get_session_token().expect(
         "Error: you need to setup your AOC token using \"cargo aoc credentials {token}\"",
     );
 
     let pm = ProjectManager::new()?;
 
-    let formated_token = format!("session={}", token);
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, CARGO_AOC_USER_AGENT.parse().unwrap());
-    headers.insert(COOKIE, formated_token.parse().unwrap());
-
-    let generate = args.generate;
     if args.all {
         let year = args
             .year
             .expect("Need to specify a year to run cargo-aoc input --all");
-        {
-            let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
-            rt.block_on(async {
-                let client = reqwest::Client::builder()
-                    .default_headers(headers)
-                    .build()
-                    .unwrap();
-                let client = Arc::new(client);
-                let mut tasks = Vec::new();
-                for day in 1..26u32 {
-                    let client = client.clone();
-                    let pm = pm.clone();
-                    tasks.push(tokio::spawn(async move {
-                        let date = AOCDate { day, year };
-                        match download_input_async(date, &client).await {
-                            Ok(_) => println!("Successfully downloaded day {day}"),
-                            Err(e) => eprintln!("{e}"),
-                        };
-                        if generate {
-                            match codegen(day, &pm) {
-                                Ok(_) => {
-                                    println!("Successfully generated boilerplate for day {day}")
-                                }
-                                Err(e) => eprintln!("{e}"),
-                            }
-                        }
-                        day
-                    }));
-                }
-                let mut results = Vec::new();
-                for task in tasks {
-                    let r = task.await;
-                    if let Ok(r) = r {
-                        results.push(r);
+        let now = chrono::Utc::now();
+        let days: Vec<u32> = (1..=calendar::days(year))
+            .filter(|&day| calendar::unlock_time(year, day) <= now)
+            .collect();
+
+        // The downloads share the throttling of the requests, whatever the number of workers
+        let pending = Mutex::new(days.iter().copied());
+        thread::scope(|s| {
+            for _ in 0..args.jobs.unwrap_or(INPUT_JOBS).max(1) {
+                s.spawn(|| loop {
+                    let Some(day) = pending.lock().unwrap().next() else {
+                        break;
+                    };
+                    let date = AOCDate { day, year };
+                    if Path::new(&date.filename()).exists() && !args.force {
+                        println!("Day {day} already downloaded");
+                        continue;
                     }
+                    let downloaded = if args.force {
+                        fetch_input(date)
+                    } else {
+                        download_input(date)
+                    };
+                    match downloaded {
+                        Ok(_) => println!("Successfully downloaded day {day}"),
+                        Err(e) => eprintln!("Day {day}: {e}"),
+                    };
+                });
+            }
+        });
+
+        for day in days {
+            if args.generate {
+                match codegen(day, &pm) {
+                    Ok(_) => println!("Successfully generated boilerplate for day {day}"),
+                    Err(e) => eprintln!("{e}"),
                 }
-                results.sort_unstable();
-                for i in results {
-                    let _ =
-                        update_lib_rs(i, &pm).map_err(|e| eprintln!("Couldn't update lib.rs: {e}"));
-                }
-            });
-        };
+            }
+            let _ = update_lib_rs(day, &pm).map_err(|e| eprintln!("Couldn't update lib.rs: {e}"));
+        }
         return Ok(());
     }
 
-    // Creates the AOCDate struct from the arguments (defaults to today...)
-    let date: AOCDate = AOCDate::new(args);
-    download_input(date)?;
+    // Creates the AOCDate struct from the arguments (defaults to today, or the next unlock)
+    let date = match (args.wait, args.day, args.year) {
+        (true, None, None) => {
+            let (year, day, _) = calendar::next_unlock(chrono::Utc::now());
+            AOCDate { day, year }
+        }
+        _ => AOCDate::new(args),
+    };
+    if args.wait {
+        wait_and_download(date)?;
+    } else if args.force {
+        fetch_input(date)?;
+    } else {
+        download_input(date)?;
+    }
 
-    if generate {
+    if args.generate {
         update_lib_rs(date.day, &pm)?;
         codegen(date.day, &pm)?;
         println!("Successfully generated boilerplate for {}", date.day);
@@ -115,6 +263,282 @@ pub fn execute_input(args: &Input) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Executes the "clean" subcommand of the app
+pub fn execute_clean(args: &Clean) -> Result<(), Box<dyn Error>> {
+    // Without any category selected, only the generated crates are removed
+    let default = !(args.build || args.bench || args.inputs);
+
+    if args.all || default || args.build {
+        remove_dir("target/aoc/aoc-autobuild")?;
+    }
+    if args.all || default || args.bench {
+        remove_dir("target/aoc/aoc-autobench")?;
+    }
+    if (args.all || args.inputs) && Config::get().input_path.is_some() {
+        // The inputs can be anywhere, every possible one is looked for
+        for year in 2015..=AOCDate::today().year {
+            for day in 1..=calendar::days(year) {
+                let path = AOCDate { day, year }.filename();
+                if Path::new(&path).exists() {
+                    fs::remove_file(&path)?;
+                    println!("Removed {}", path);
+                }
+            }
+        }
+    } else if args.all || args.inputs {
+        // Only the downloaded inputs are removed, answers & examples are kept
+        for year in fs::read_dir(Config::get().account_dir())
+            .into_iter()
+            .flatten()
+        {
+            let year = year?;
+            if !year.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(year.path())? {
+                let path = file?.path();
+                let name = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or_default();
+                if name.starts_with("day") && name.ends_with(".txt") {
+                    fs::remove_file(&path)?;
+                    println!("Removed {}", path.display());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_dir(dir: &str) -> Result<(), Box<dyn Error>> {
+    if Path::new(dir).exists() {
+        fs::remove_dir_all(dir)?;
+        println!("Removed {}", dir);
+    }
+    Ok(())
+}
+
+/// Executes the "export" subcommand of the app.
+///
+/// The runner already embeds the inputs with `include_str!`, so it runs anywhere once built.
+pub fn execute_export(args: &Export) -> Result<(), Box<dyn Error>> {
+    let pm = ProjectManager::new()?;
+    let day_parts = pm.build_project()?;
+    let year = day_parts.year;
+
+    let mut days: Vec<_> = day_parts
+        .iter()
+        .map(|dp| dp.day)
+        .filter(|day| args.day.as_ref().is_none_or(|days| days.contains(day)))
+        .collect();
+    days.sort();
+    days.dedup();
+    if days.is_empty() {
+        return Err("No matching day found".into());
+    }
+
+    for &day in &days {
+        if Config::get().input(day, None).is_none() {
+            download_input(AOCDate {
+                day: u32::from(day.0),
+                year: year as i32,
+            })?;
+        }
+    }
+
+    let template = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/src/runner.rs.tpl"
+    ));
+    let body: String = days
+        .iter()
+        .map(|&day| runners_body(&day_parts, day, None, None, template))
+        .collect();
+
+    titles::fetch(year, &days);
+    let profile = Config::get().profile(None, args.profile.as_ref());
+    write_autobuild(&pm, year, &days, &body, None, &profile, true);
+
+    let status = process::Command::new("cargo")
+        .arg("build")
+        .args(profile.cargo_args("release"))
+        .current_dir("target/aoc/aoc-autobuild")
+        .status()?;
+    if !status.success() {
+        return Err(format!(
+            "cargo build failed with code {}",
+            status.code().unwrap_or(-1)
+        )
+        .into());
+    }
+
+    let built = format!(
+        "target/aoc/aoc-autobuild/target/{}/aoc-autobuild{}",
+        profile.target_dir("release"),
+        env::consts::EXE_SUFFIX
+    );
+    let output = match &args.output {
+        Some(output) => output.clone(),
+        None => format!("aoc-{}{}", year, env::consts::EXE_SUFFIX),
+    };
+    fs::copy(&built, &output).map_err(|e| format!("Could not copy {}: {}", built, e))?;
+    println!(
+        "Exported the solutions of {} day(s) of {} to {}",
+        days.len(),
+        year,
+        output
+    );
+
+    Ok(())
+}
+
+/// Executes the "doctor" subcommand of the app
+pub fn execute_doctor() -> Result<(), Box<dyn Error>> {
+    let src_dir = ProjectManager::new()
+        .ok()
+        .and_then(|pm| pm.lib_path)
+        .as_deref()
+        .map(Path::new)
+        .and_then(|lib_path| lib_path.parent())
+        .unwrap_or(Path::new("src"))
+        .to_path_buf();
+
+    let today = AOCDate::today();
+    let project_year = DayParts::load().ok().map(|dp| dp.year);
+    let year = project_year
+        .map(|y| y as i32)
+        .or(Config::get().year)
+        .unwrap_or(today.year);
+
+    let mut checks = vec![
+        Check::new("network", doctor::network()),
+        Check::new(
+            "session",
+            doctor::session(CredentialsManager::new().get_session_token(), year),
+        ),
+        Check::new("dependencies", doctor::dependencies()),
+        Check::new("year", doctor::year(project_year, today.year)),
+        Check::new(
+            "input directory",
+            doctor::input_directory(AOCDate { day: 1, year }),
+        ),
+    ];
+    if let Ok(last_change) = last_modified(&src_dir) {
+        checks.push(Check::new(
+            "runner metadata",
+            doctor::metadata(&src_dir, last_change),
+        ));
+    }
+
+    for check in &checks {
+        check.print();
+    }
+
+    if checks.iter().any(Check::is_error) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Executes the "init" subcommand of the app
+pub fn execute_init(args: &Init) -> Result<(), Box<dyn Error>> {
+    let name = args
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("advent-of-code-{}", args.year));
+    let slug = name.replace('-', "_");
+    let root = Path::new(args.path.as_deref().unwrap_or(&name));
+
+    if root.join("Cargo.toml").exists() {
+        Err(format!("{} already contains a Cargo.toml", root.display()))?
+    }
+
+    let year = args.year.to_string();
+    fs::create_dir_all(root.join("src"))?;
+    fs::create_dir_all(root.join("input").join(&year))?;
+
+    let cargo_content = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/init/Cargo.toml.tpl"
+    ))
+    .replace("{CRATE_NAME}", &name);
+    fs::write(root.join("Cargo.toml"), cargo_content)?;
+
+    let lib_content = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/init/lib.rs.tpl"
+    ))
+    .replace("{YEAR}", &year);
+    fs::write(root.join("src/lib.rs"), lib_content)?;
+
+    let main_content = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/init/main.rs.tpl"
+    ))
+    .replace("{CRATE_SLUG}", &slug);
+    fs::write(root.join("src/main.rs"), main_content)?;
+
+    // Keeps an existing .gitignore, only adding the missing entries
+    let gitignore_path = root.join(".gitignore");
+    let mut gitignore = fs::read_to_string(&gitignore_path).unwrap_or_default();
+    for entry in include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/init/gitignore.tpl"
+    ))
+    .lines()
+    {
+        if !gitignore.lines().any(|line| line == entry) {
+            if !gitignore.is_empty() && !gitignore.ends_with('\n') {
+                gitignore.push('\n');
+            }
+            gitignore += entry;
+            gitignore.push('\n');
+        }
+    }
+    fs::write(gitignore_path, gitignore)?;
+
+    println!(
+        "Successfully created {} for Advent of Code {} in {}",
+        name,
+        year,
+        root.display()
+    );
+    println!("Run `cargo aoc new 1` in it to start solving the first day");
+
+    Ok(())
+}
+
+/// Executes the "new" subcommand of the app
+pub fn execute_new(args: &New) -> Result<(), Box<dyn Error>> {
+    let pm = ProjectManager::new()?;
+
+    let today = AOCDate::today();
+    // Prefer the year of the last build, if the project was already built.
+    let year = args
+        .year
+        .or_else(|| DayParts::load().ok().map(|dp| dp.year as i32))
+        .or(Config::get().year)
+        .unwrap_or(today.year);
+
+    let date = AOCDate {
+        day: u32::from(args.day.0),
+        year,
+    };
+
+    codegen(date.day, &pm)?;
+    update_lib_rs(date.day, &pm)?;
+    println!("Successfully generated boilerplate for day {}", date.day);
+
+    match download_input(date) {
+        Ok(()) => println!("Successfully downloaded day {}", date.day),
+        Err(e) => eprintln!("Couldn't download the input: {e}"),
+    }
+
+    Ok(())
+}
+
 fn update_lib_rs(day: u32, pm: &ProjectManager) -> Result<(), Box<dyn Error>> {
     let lib_rs_path = Path::new(pm.lib_path.as_deref().unwrap_or("src/lib.rs"));
     if !lib_rs_path.exists() {
@@ -154,98 +578,344 @@ fn codegen(day: u32, pm: &ProjectManager) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-// The client should have the appropriate headers set
-async fn download_input_async(
-    date: AOCDate,
-    client: &reqwest::Client,
-) -> Result<(), Box<dyn error::Error>> {
+fn download_input(date: AOCDate) -> Result<(), Box<dyn error::Error>> {
     let filename = date.filename();
     let filename = Path::new(&filename);
 
-    if filename.exists() {
-        return Ok(());
+    // An error page saved by a previous version is downloaded again
+    if let Ok(input) = fs::read_to_string(filename) {
+        if input_error(date, &input).is_none() {
+            return Ok(());
+        }
     }
 
-    let response = client.get(date.request_url()).send().await?;
+    // The compressed or encrypted inputs are unpacked to be used, instead of being downloaded again
+    if let Some(packed) = date.packed_filename().map(PathBuf::from) {
+        if packed.exists() {
+            let input = unpack(&packed)?;
+            if let Some(dir) = filename.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(filename, input)?;
+            return Ok(());
+        }
+    }
+    fetch_input(date)
+}
 
-    match response.status() {
-        StatusCode::OK => {
-            let dir = date.directory();
-            // Creates the file-tree to store inputs
-            // TODO: Maybe use crate's infos to get its root in the filesystem ?
-            fs::create_dir_all(&dir)?;
-
-            // Gets the body from the response and outputs everything to a file
-            let body = response
-                .text()
-                .await
-                .map_err(|e| format!("Can't convert response to text: {e:?}"))?;
-            let mut file = File::create(filename)?;
-            file.write_all(body.as_bytes())?;
+/// Stores an input compressed and encrypted, as set in aoc.toml
+fn pack(input: &[u8], path: &Path) -> Result<(), Box<dyn error::Error>> {
+    let input = if compression::enabled() {
+        compression::compress(input)?
+    } else {
+        input.to_vec()
+    };
+    match encryption::config() {
+        Some(config) => encryption::encrypt(config, &input, path),
+        None => Ok(fs::write(path, input)?),
+    }
+}
+
+/// Reads an input stored compressed and encrypted, as set in aoc.toml
+fn unpack(path: &Path) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    let input = match encryption::config() {
+        Some(config) => encryption::decrypt(config, path)?,
+        None => fs::read(path)?,
+    };
+    if compression::enabled() {
+        compression::decompress(&input)
+    } else {
+        Ok(input)
+    }
+}
+
+/// Creates `{input_dir}/.gitignore`, so that the inputs and the puzzles aren't committed.
+///
+/// Nothing is done when it exists, when the inputs are encrypted or stored elsewhere with
+/// `input_path`, or with `ignore_inputs = false`.
+fn ignore_inputs() -> Result<(), Box<dyn error::Error>> {
+    let config = Config::get();
+    if !config.ignore_inputs || config.encryption.is_some() || config.input_path.is_some() {
+        return Ok(());
+    }
+
+    let path = Path::new(config.input_dir()).join(".gitignore");
+    fs::create_dir_all(config.input_dir())?;
+    // Several inputs can be downloaded at once, only one of them creates the file
+    match File::options().write(true).create_new(true).open(&path) {
+        Ok(mut file) => {
+            file.write_all(
+                b"# The Advent of Code asks not to publish the inputs and the puzzles\n\
+                  day*.txt\n\
+                  day*.txt.zst\n\
+                  puzzles/\n",
+            )?;
+            println!(
+                "Created {}, so that the inputs aren't committed",
+                path.display()
+            );
             Ok(())
         }
-        StatusCode::NOT_FOUND => Err(format!("Day {} not yet ready", date.day))?,
-        sc => Err(format!(
-            "Could not find corresponding input. Is the token correct?\n\
-                Status: {}\n\n\
-                Message: {}",
-            sc,
-            response.text().await.unwrap_or_else(|_| String::new())
-        ))?,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(()),
+        Err(e) => Err(e.into()),
     }
 }
 
-fn download_input(date: AOCDate) -> Result<(), Box<dyn error::Error>> {
-    let filename = date.filename();
-    let filename = Path::new(&filename);
+/// Tells why a downloaded input is an error page of adventofcode.com instead of a puzzle input
+fn input_error(date: AOCDate, input: &str) -> Option<String> {
+    if input.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        Some(calendar::not_unlocked(date.year, date.day))
+    } else if input.contains("Puzzle inputs differ by user") {
+        Some(credentials::session_expired_error())
+    } else if input.trim().is_empty() {
+        Some(format!("The input of day {} is empty", date.day))
+    } else if ["<!DOCTYPE", "<html"]
+        .iter()
+        .any(|tag| input.trim_start().starts_with(tag))
+    {
+        Some(format!(
+            "adventofcode.com sent a web page instead of the input of day {}, try again later",
+            date.day
+        ))
+    } else {
+        None
+    }
+}
 
-    if filename.exists() {
-        return Ok(());
+/// Downloads an input, replacing the one already downloaded, and stores its checksum
+fn fetch_input(date: AOCDate) -> Result<(), Box<dyn error::Error>> {
+    // Another tool may have downloaded it already
+    let token = CredentialsManager::new().get_session_token();
+    if let Some(input) = token.as_ref().ok().and_then(|t| shared_cache::read(date, t)) {
+        return store_input(date, &input);
     }
 
-    let token = CredentialsManager::new().get_session_token()?;
-    // Creates an HTTP Client
-    let client = reqwest::blocking::Client::new();
-    // Cookie formatting ...
-    let formated_token = format!("session={}", token);
-
-    let response = client
-        .get(date.request_url())
-        .header(USER_AGENT, CARGO_AOC_USER_AGENT)
-        .header(COOKIE, formated_token)
-        .send()?;
-
-    match response.status() {
-            StatusCode::OK => {
-                let dir = date.directory();
-                // Creates the file-tree to store inputs
-                // TODO: Maybe use crate's infos to get its root in the filesystem ?
-                fs::create_dir_all(dir)?;
+    if http::offline() {
+        return Err(format!(
+            "The input of day {} of {} can't be downloaded in offline mode",
+            date.day, date.year
+        )
+        .into());
+    }
+
+    // The Advent of Code asks not to request the inputs before they unlock
+    if !calendar::is_unlocked(date.year, date.day) {
+        return Err(NotUnlocked(calendar::not_unlocked(date.year, date.day)).into());
+    }
 
-                // Gets the body from the response and outputs everything to a file
-                let body = response.text()?;
-                let mut file = File::create(filename)?;
-                file.write_all(body.as_bytes())?;
+    let token = token?;
+    let response = http::get(&Endpoint::INPUT, &date.request_url(), Some(&token))?;
+
+    match response.status {
+        StatusCode::OK => {
+            if let Some(error) = input_error(date, &response.body) {
+                return Err(error.into());
+            }
+
+            store_input(date, &response.body)?;
+            shared_cache::write(date, &token, &response.body)?;
+        }
+        StatusCode::NOT_FOUND => {
+            return Err(NotUnlocked(calendar::not_unlocked(date.year, date.day)).into())
+        }
+        sc => {
+            if credentials::session_expired(sc, &response.body) {
+                return Err(credentials::session_expired_error().into());
             }
-            sc => return Err(format!(
+            return Err(format!(
                 "Could not find corresponding input. Are the day, year, and token correctly set ? Status: {}\
-                 Message: {}", sc, response.text().unwrap_or_else(|_| String::new())
-            ).into()),
+                 Message: {}", sc, response.body
+            ).into());
         }
+    }
 
     Ok(())
 }
 
-pub fn execute_default(args: &Cli) -> Result<(), Box<dyn error::Error>> {
-    let pm = ProjectManager::new()?;
+/// Stores an input in the input directory, with its checksum
+fn store_input(date: AOCDate, input: &str) -> Result<(), Box<dyn error::Error>> {
+    let filename = PathBuf::from(date.filename());
+    // Creates the file-tree to store inputs
+    // TODO: Maybe use crate's infos to get its root in the filesystem ?
+    if let Some(dir) = filename.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    ignore_inputs()?;
+
+    // Outputs the input to a file
+    let mut file = File::create(&filename)?;
+    file.write_all(input.as_bytes())?;
+    checksums::record(date, input.as_bytes())?;
+
+    if let Some(packed) = date.packed_filename().map(PathBuf::from) {
+        if let Some(dir) = packed.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        pack(input.as_bytes(), &packed)?;
+    }
+
+    Ok(())
+}
+
+/// Compares the downloaded inputs to their checksums, failing if one of them was modified
+fn check_inputs(dates: &[AOCDate]) -> Result<(), Box<dyn error::Error>> {
+    let mut modified = Vec::new();
+    for date in dates {
+        let checksums = Checksums::load(date.year)?;
+        let state = match checksums.state(*date) {
+            InputState::Ok => color::paint(color::GREEN, "ok"),
+            InputState::Modified => {
+                modified.push(date.day.to_string());
+                color::paint(color::RED, "modified")
+            }
+            InputState::Unknown => color::paint(color::YELLOW, "no checksum"),
+            InputState::Missing => "missing".to_string(),
+        };
+        println!("Day {:>2} : {}", date.day, state);
+    }
+
+    if !modified.is_empty() {
+        return Err(format!(
+            "The input of day {} changed since it was downloaded, \
+             download it again with `cargo aoc input -d {{day}} --force`",
+            modified.join(", ")
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Sleeps until a puzzle unlocks, then downloads its input.
+///
+/// The input can take a moment to be served after the unlock, so the download is retried
+/// a few times, waiting longer each time.
+fn wait_and_download(date: AOCDate) -> Result<(), Box<dyn error::Error>> {
+    calendar::wait(date.year, date.day)?;
+    notify::send(
+        "Advent of Code",
+        &format!("Day {} of {} is unlocked", date.day, date.year),
+    );
+
+    // The input can be missing for a few seconds after the unlock. The network & server errors
+    // are already retried by http::get.
+    let mut delay = Duration::from_secs(1);
+    for attempt in 1..=UNLOCK_ATTEMPTS {
+        match download_input(date) {
+            Ok(()) => break,
+            Err(e) if attempt < UNLOCK_ATTEMPTS && e.is::<NotUnlocked>() => {
+                eprintln!("{}", e);
+                eprintln!("Retrying in {}s...", delay.as_secs());
+                std::thread::sleep(delay);
+                delay = (delay * 2).min(Duration::from_secs(30));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    println!("Successfully downloaded day {}", date.day);
+    Ok(())
+}
+
+pub fn execute_default(args: &Run) -> Result<(), Box<dyn error::Error>> {
+    args.output.check(
+        "run",
+        &[
+            OutputFormat::Text,
+            OutputFormat::Json,
+            OutputFormat::Csv,
+            OutputFormat::Github,
+        ],
+    )?;
+    memory::init(args.memory);
+
+    // The input is saved to a file, the runner including it when it is built
+    let with_stdin;
+    let args = if args.stdin {
+        with_stdin = Run {
+            input: Some(save_stdin_input()?),
+            stdin: false,
+            ..args.clone()
+        };
+        &with_stdin
+    } else {
+        args
+    };
+
+    if args.all_years {
+        return execute_all_years(args);
+    }
+
+    if args.example {
+        let day = args
+            .day
+            .as_ref()
+            .map(|days| days.single().ok_or("--example runs a single day"))
+            .transpose()?;
+        return execute_test(&Test {
+            day,
+            part: args.part,
+            profile: args.profile.clone(),
+        });
+    }
+
+    let mut pm = ProjectManager::new()?;
+    pm.quiet = args.quiet;
 
     let mut day_parts = pm.build_project()?;
 
+    // Without any selection, lets the user explore the implemented days outside of the event
+    let interactive = args.day.is_none()
+        && args.part.is_none()
+        && !args.all
+        && !args.generate
+        && !args.quiet
+        && args.output == OutputFormat::Text
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+        && AOCDate::current_puzzle().is_none();
+    let picked;
+    let args = if interactive {
+        let Some(choice) = picker::pick(&picker::choices(&day_parts))? else {
+            return Ok(());
+        };
+        picked = Run {
+            day: Some(choice.day.into()),
+            part: choice.part,
+            alt: choice.alt,
+            ..args.clone()
+        };
+        &picked
+    } else {
+        args
+    };
+
     let part = args.part;
-    let day = args
-        .day
-        .unwrap_or_else(|| day_parts.last().expect("No implementation found").day);
+    if args.all {
+        let mut days: Vec<_> = day_parts.iter().map(|dp| dp.day).collect();
+        days.sort();
+        days.dedup();
+        return execute_days(args, &days, &pm, day_parts);
+    }
+
+    let day = match &args.day {
+        Some(days) => match days.single() {
+            Some(day) => day,
+            None => return execute_days(args, days, &pm, day_parts),
+        },
+        None => day_parts.last().expect("No implementation found").day,
+    };
+
+    // Machine readable outputs need the results of the runner
     let year = day_parts.year;
+    if args.output != OutputFormat::Text
+        || args.quiet
+        || args.timeout.is_some()
+        || args.runs > 1
+        || args.memory
+        || args.clipboard
+    {
+        return execute_days(args, &[day], &pm, day_parts);
+    }
 
     let date = AOCDate {
         day: u32::from(day.0),
@@ -260,65 +930,1511 @@ pub fn execute_default(args: &Cli) -> Result<(), Box<dyn error::Error>> {
         day_parts = pm.build_project()?;
     }
 
-    let cargo_content = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/Cargo-run.toml.tpl"
-    ))
-    .replace("{CRATE_NAME}", &pm.name)
-    .replace(
-        "{PROFILE}",
-        if args.profile {
-            "[profile.release]\ndebug = true"
-        } else {
-            ""
-        },
-    );
-
     let template = include_str!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/template/src/runner.rs.tpl"
     ));
 
-    let mut body = String::new();
-    for dp in day_parts.iter().filter(|dp| dp.day == day).filter(|dp| {
-        if let Some(p) = part {
-            dp.part == p
-        } else {
-            true
-        }
-    }) {
-        let (name, display) = if let Some(n) = &dp.name {
-            (
-                format!("day{}_part{}_{}", dp.day.0, dp.part.0, n.to_lowercase()),
-                format!("Day {} - Part {} - {}", dp.day.0, dp.part.0, n),
-            )
+    let body = runners_body(&day_parts, day, part, args.alt.as_deref(), template);
+
+    if body.is_empty() {
+        return Err("No matching day & part found".into());
+    }
+
+    if Config::get().input(day, args.input.as_deref()).is_none() {
+        download_input(date)?;
+    }
+
+    titles::fetch(year, &[day]);
+    let profile = Config::get().profile(Some(day), args.profile.as_ref());
+    write_autobuild(
+        &pm,
+        year,
+        &[day],
+        &body,
+        args.input.as_deref(),
+        &profile,
+        true,
+    );
+
+    // The runner also writes its results to a file for the answers check, its output being shown
+    // as it is
+    let results_path = env::current_dir()?.join("target/aoc/results.txt");
+    let _ = fs::remove_file(&results_path);
+
+    let started = Instant::now();
+    let status = process::Command::new("cargo")
+        .arg("run")
+        .args(profile.cargo_args("release"))
+        .current_dir("target/aoc/aoc-autobuild")
+        .env(RESULTS_ENV, &results_path)
+        .spawn()
+        .expect("Failed to run cargo")
+        .wait()
+        .expect("Failed to wait for cargo");
+    notify::finished(status.success(), started.elapsed());
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(-1));
+    }
+
+    let answers = Answers::load(year as i32)?;
+    if answers.contains_day(day) {
+        let output = fs::read_to_string(&results_path).unwrap_or_default();
+        check_answers(&answers, &PartResult::parse_all(&output)?);
+    }
+    Ok(())
+}
+
+/// Exits with [`WRONG_ANSWER_EXIT_CODE`] when a part failed, or gave another answer than the
+/// stored one
+fn check_answers(answers: &Answers, results: &[PartResult]) {
+    let failures: Vec<_> = results.iter().filter(|r| answers.is_failure(r)).collect();
+    if failures.is_empty() {
+        return;
+    }
+    for result in failures.iter().filter(|r| r.status == Status::Ok) {
+        eprintln!(
+            "{} : wrong answer, expected {} but got {}",
+            result.display(),
+            answers.get(result.day, result.part).unwrap_or_default(),
+            result.answer
+        );
+    }
+    process::exit(WRONG_ANSWER_EXIT_CODE);
+}
+
+/// Executes the "today" subcommand of the app
+pub fn execute_today(args: &Today) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+
+    let date = if args.wait {
+        let (year, day, _) = calendar::next_unlock(chrono::Utc::now());
+        let date = AOCDate { day, year };
+        wait_and_download(date)?;
+        date
+    } else {
+        let date = AOCDate::current_puzzle().ok_or("Advent of Code is not running today")?;
+        download_input(date)?;
+        date
+    };
+
+    let day = Day(date.day as u8);
+    let day_parts = pm.build_project()?;
+    if !day_parts.iter().any(|dp| dp.day == day) {
+        if args.wait {
+            // Like `cargo aoc new`, as there is nothing to run yet
+            codegen(date.day, &pm)?;
+            update_lib_rs(date.day, &pm)?;
+            println!("Successfully generated boilerplate for day {}", date.day);
+            return Ok(());
+        }
+        Err(format!(
+            "Day {} is not implemented yet, use `cargo aoc new {}` to start",
+            day.0, day.0
+        ))?
+    }
+
+    let run = Run {
+        day: Some(day.into()),
+        input: args.input.clone(),
+        profile: args.profile.clone(),
+        ..Default::default()
+    };
+
+    execute_default(&run)
+}
+
+/// Executes the "status" subcommand of the app
+pub fn execute_status() -> Result<(), Box<dyn error::Error>> {
+    let day_parts = ProjectManager::new()?.build_project()?;
+    let year = day_parts.year as i32;
+    let answers = Answers::load(year)?;
+
+    let account = match CredentialsManager::new().get_session_token() {
+        Ok(token) => account(year, &token),
+        Err(_) => "no session token, set it using `cargo aoc credentials {token}`".into(),
+    };
+    println!("AOC {}", year);
+    println!("Account: {}\n", account);
+
+    const STATUSES: [&str; 3] = ["solved", "implemented", "missing"];
+
+    // The unlocked days, and the ones implemented in advance
+    let now = chrono::Utc::now();
+    let days: Vec<_> = (1..=calendar::days(year))
+        .filter(|&day| {
+            calendar::unlock_time(year, day) <= now
+                || day_parts.iter().any(|dp| u32::from(dp.day.0) == day)
+        })
+        .collect();
+
+    let mut counts = [0; 3];
+    println!("{:>4}  {:<8} {:<12} Part 2", "Day", "Input", "Part 1");
+    for &day in &days {
+        let input = if Path::new(&AOCDate { day, year }.filename()).exists() {
+            "cached"
         } else {
-            (
-                format!("day{}_part{}", dp.day.0, dp.part.0),
-                format!("Day {} - Part {}", dp.day.0, dp.part.0),
-            )
+            "missing"
+        };
+
+        let [part1, part2] = [Part(1), Part(2)].map(|part| {
+            let day = Day(day as u8);
+            let status = if answers.get(day, part).is_some() {
+                0
+            } else if day_parts.iter().any(|dp| dp.day == day && dp.part == part) {
+                1
+            } else {
+                2
+            };
+            counts[status] += 1;
+            STATUSES[status]
+        });
+
+        println!("{:>4}  {:<8} {:<12} {}", day, input, part1, part2);
+    }
+
+    let summary: Vec<_> = STATUSES
+        .iter()
+        .zip(counts)
+        .map(|(status, count)| format!("{} {}", count, status))
+        .collect();
+    println!("\nParts: {}", summary.join(", "));
+
+    Ok(())
+}
+
+/// Executes the "list" subcommand of the app
+pub fn execute_list() -> Result<(), Box<dyn error::Error>> {
+    let mut day_parts = ProjectManager::new()?.build_project()?;
+    day_parts.sort();
+
+    let year = day_parts.year;
+    println!("AOC {}", year);
+    println!("{:>4} {:>5}  {:<8} Implementations", "Day", "Part", "Input");
+
+    for dp in day_parts.iter() {
+        if dp.name.is_some()
+            && day_parts
+                .iter()
+                .any(|other| other.day == dp.day && other.part == dp.part && other < dp)
+        {
+            // Already listed with the previous implementations
+            continue;
+        }
+
+        let implementations = day_parts
+            .iter()
+            .filter(|other| other.day == dp.day && other.part == dp.part)
+            .map(|other| other.name.as_deref().unwrap_or("(default)"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let date = AOCDate {
+            day: u32::from(dp.day.0),
+            year: year as i32,
+        };
+        let input = if Path::new(&date.filename()).exists() {
+            "cached"
+        } else {
+            "missing"
+        };
+
+        println!(
+            "{:>4} {:>5}  {:<8} {}",
+            dp.day.0, dp.part.0, input, implementations
+        );
+    }
+
+    Ok(())
+}
+
+/// Executes the "open" subcommand of the app
+pub fn execute_open(args: &Open) -> Result<(), Box<dyn error::Error>> {
+    let day_parts = ProjectManager::new()?.build_project()?;
+    let year = day_parts.year as i32;
+
+    // Defaults to today's puzzle during the event, and to the last implemented day otherwise
+    let day = match (args.day, AOCDate::current_puzzle()) {
+        (Some(day), _) => u32::from(day.0),
+        (None, Some(today)) if today.year == year => today.day,
+        (None, _) => u32::from(day_parts.last().ok_or("No implementation found")?.day.0),
+    };
+
+    let url = AOCDate { day, year }.puzzle_url();
+    println!("Opening {}", url);
+    webbrowser::open(&url)?;
+
+    Ok(())
+}
+
+/// Executes the "calendar" subcommand of the app
+pub fn execute_calendar(args: &Calendar) -> Result<(), Box<dyn error::Error>> {
+    // Also works outside of a project, without the implemented days
+    let day_parts = ProjectManager::new()
+        .and_then(|pm| pm.build_project())
+        .ok();
+    let year = args
+        .year
+        .or(day_parts.as_ref().map(|dp| dp.year as i32))
+        .or(Config::get().year)
+        .unwrap_or(AOCDate::today().year);
+
+    let answers = Answers::load(year)?;
+    println!("{}", calendar::render(year, day_parts.as_ref(), &answers));
+
+    // Counts down until the puzzle unlocks only when asked, it can be months away
+    let (unlock_year, unlock_day, unlock) = calendar::next_unlock(chrono::Utc::now());
+    if args.wait {
+        calendar::wait(unlock_year, unlock_day)?;
+        println!("Day {} of {} is unlocked!", unlock_day, unlock_year);
+    } else {
+        println!(
+            "Day {} of {} unlocks in {}",
+            unlock_day,
+            unlock_year,
+            calendar::countdown(unlock - chrono::Utc::now())
+        );
+    }
+
+    Ok(())
+}
+
+/// Executes the "self-update" subcommand of the app
+pub fn execute_self_update(args: &SelfUpdate) -> Result<(), Box<dyn error::Error>> {
+    let latest = self_update::latest_version()?;
+    let current = self_update::CURRENT_VERSION;
+
+    if !self_update::is_newer(&latest, current) && !args.force {
+        println!("cargo-aoc {} is up to date", current);
+        return Ok(());
+    }
+    if args.check {
+        println!("cargo-aoc {} is available (installed: {})", latest, current);
+        return Ok(());
+    }
+
+    println!("Installing cargo-aoc {} (installed: {})...", latest, current);
+    let status = process::Command::new("cargo")
+        .args(["install", "cargo-aoc", "--locked", "--force", "--version"])
+        .arg(&latest)
+        .status()?;
+    if !status.success() {
+        Err("cargo install failed, see the errors above")?
+    }
+
+    println!("Successfully updated cargo-aoc to {}", latest);
+    Ok(())
+}
+
+/// Executes the "stars" subcommand of the app
+pub fn execute_stars(args: &Stars) -> Result<(), Box<dyn error::Error>> {
+    let token = CredentialsManager::new().get_session_token()?;
+    let year = args
+        .year
+        .or_else(|| DayParts::load().ok().map(|dp| dp.year as i32))
+        .or(Config::get().year)
+        .unwrap_or(AOCDate::today().year);
+
+    let days = stars::fetch(year, &token)?;
+    print!("{}", stars::render(year, &days));
+
+    Ok(())
+}
+
+/// Executes the "puzzle" subcommand of the app
+pub fn execute_puzzle(args: &Puzzle) -> Result<(), Box<dyn error::Error>> {
+    let day_parts = ProjectManager::new()?.build_project()?;
+    let year = day_parts.year as i32;
+
+    // Defaults to today's puzzle during the event, and to the last implemented day otherwise
+    let day = match (args.day, AOCDate::current_puzzle()) {
+        (Some(day), _) => u32::from(day.0),
+        (None, Some(today)) if today.year == year => today.day,
+        (None, _) => u32::from(day_parts.last().ok_or("No implementation found")?.day.0),
+    };
+
+    // Logged out, only the part 1 is shown
+    let token = CredentialsManager::new().get_session_token().ok();
+    let description = puzzle::load(AOCDate { day, year }, token.as_deref(), args.refresh)?;
+    print!("{}", puzzle::render(&description));
+
+    let day = Day(day as u8);
+    if examples::save_extracted(year, day, &description)? {
+        println!(
+            "The example was saved in {}, for `cargo aoc test`",
+            examples::path(year, day).display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs several days at once, and prints their results in a table
+fn execute_days(
+    args: &Run,
+    days: &[Day],
+    pm: &ProjectManager,
+    mut day_parts: DayParts,
+) -> Result<(), Box<dyn error::Error>> {
+    if args.generate {
+        for day in days.iter() {
+            update_lib_rs(u32::from(day.0), pm)?;
+            codegen(u32::from(day.0), pm)?;
+            println!("Successfully generated boilerplate for {}", day.0);
+        }
+        // Rebuild to include newly generated days
+        day_parts = pm.build_project()?;
+    }
+
+    let mut parts: Vec<_> = day_parts
+        .iter()
+        .filter(|dp| days.contains(&dp.day))
+        .filter(|dp| args.part.is_none_or(|p| dp.part == p))
+        .filter(|dp| args.alt.is_none() || dp.name == args.alt)
+        .collect();
+    parts.sort();
+
+    if parts.is_empty() {
+        return Err("No matching day & part found".into());
+    }
+
+    let options = RunnerOptions {
+        input: args.input.as_deref(),
+        profile: args.profile.as_ref(),
+        jobs: args.jobs.unwrap_or(1),
+        timeout: args.timeout,
+        runs: args.runs,
+    };
+    let results = run_results(pm, day_parts.year, &parts, options)?;
+
+    match args.output {
+        OutputFormat::Text if args.quiet => results::print_quiet(&results),
+        OutputFormat::Text => {
+            titles::fetch(day_parts.year, days);
+            println!("AOC {}", day_parts.year);
+            results::print_table(&results, day_parts.year);
+            println!("\nTotal: {}", color::time(results::total_time(&results), 0));
+        }
+        OutputFormat::Json => {
+            let records: Vec<_> = results.iter().map(Record::from_result).collect();
+            output::print_json(&records)?;
+        }
+        OutputFormat::Csv => {
+            let records: Vec<_> = results.iter().map(Record::from_result).collect();
+            output::print_csv(&records);
+        }
+        OutputFormat::Github => {
+            let records: Vec<_> = results.iter().map(Record::from_result).collect();
+            output::print_github(&format!("AOC {}", day_parts.year), &records)?;
+        }
+        OutputFormat::Junit => unreachable!("checked by execute_default"),
+    }
+
+    if args.clipboard {
+        // The last part run, by its default implementation if it has one
+        let result = results
+            .iter()
+            .filter(|r| r.status == Status::Ok)
+            .max_by_key(|r| (r.day, r.part, r.name.is_none()))
+            .ok_or("No answer to copy to the clipboard")?;
+        clipboard::copy(&result.answer)?;
+        eprintln!("Copied the answer of {} to the clipboard", result.display());
+    }
+
+    check_answers(&Answers::load(day_parts.year as i32)?, &results);
+
+    Ok(())
+}
+
+/// Runs the days of every crate of a workspace, and prints their results year by year
+fn execute_all_years(args: &Run) -> Result<(), Box<dyn error::Error>> {
+    args.output.check("run --all-years", &[OutputFormat::Text])?;
+
+    let crates = workspace::solution_crates()?;
+    if crates.is_empty() {
+        return Err("No crate of the workspace holds solutions".into());
+    }
+
+    let mut total = Duration::ZERO;
+    let mut failures = 0;
+    for (year, dir) in crates {
+        env::set_current_dir(&dir)?;
+        let mut pm = ProjectManager::new()?;
+        pm.quiet = args.quiet;
+        let day_parts = pm.build_project()?;
+
+        let mut parts: Vec<_> = day_parts
+            .iter()
+            .filter(|dp| args.day.as_ref().is_none_or(|days| days.contains(&dp.day)))
+            .filter(|dp| args.part.is_none_or(|p| dp.part == p))
+            .filter(|dp| args.alt.is_none() || dp.name == args.alt)
+            .collect();
+        parts.sort();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let options = RunnerOptions {
+            input: args.input.as_deref(),
+            profile: args.profile.as_ref(),
+            jobs: args.jobs.unwrap_or(1),
+            timeout: args.timeout,
+            runs: args.runs,
+        };
+        let results = run_results(&pm, year, &parts, options)?;
+
+        titles::fetch(year, &results.iter().map(|r| r.day).collect::<Vec<_>>());
+        println!("AOC {}", year);
+        results::print_table(&results, year);
+        println!("\nTotal: {}\n", color::time(results::total_time(&results), 0));
+        total += results::total_time(&results);
+
+        let answers = Answers::load(year as i32)?;
+        for result in results.iter().filter(|r| answers.is_failure(r)) {
+            failures += 1;
+            if result.status == Status::Ok {
+                eprintln!(
+                    "{} {} : wrong answer, expected {} but got {}",
+                    year,
+                    result.display(),
+                    answers.get(result.day, result.part).unwrap_or_default(),
+                    result.answer
+                );
+            }
+        }
+    }
+
+    println!("Total of all years: {}", color::time(total, 0));
+
+    if failures > 0 {
+        process::exit(WRONG_ANSWER_EXIT_CODE);
+    }
+
+    Ok(())
+}
+
+/// Executes the "report" subcommand of the app
+pub fn execute_report(args: &Report) -> Result<(), Box<dyn error::Error>> {
+    if args.html {
+        return write_html_report(args);
+    }
+
+    let pm = ProjectManager::new()?;
+
+    let mut day_parts = pm.build_project()?;
+    day_parts.sort();
+    if day_parts.is_empty() {
+        return Err("No implementation found".into());
+    }
+
+    let parts: Vec<_> = day_parts.iter().collect();
+    let options = RunnerOptions {
+        profile: args.profile.as_ref(),
+        jobs: args.jobs.unwrap_or(1),
+        ..Default::default()
+    };
+    let results = run_results(&pm, day_parts.year, &parts, options)?;
+
+    let mut days: Vec<_> = parts.iter().map(|dp| dp.day).collect();
+    days.dedup();
+    titles::fetch(day_parts.year, &days);
+    let content = format!(
+        "## Advent of Code {}\n\n{}",
+        day_parts.year,
+        report::markdown(&results, day_parts.year, args.answers)
+    );
+
+    if let Some(readme) = &args.readme {
+        let document = fs::read_to_string(readme)
+            .map_err(|e| format!("Could not read {}: {}", readme, e))?;
+        let document = report::replace_section(&document, &content)
+            .map_err(|e| format!("Could not update {}: {}", readme, e))?;
+        fs::write(readme, document)?;
+        println!("Report section of {} updated", readme);
+    }
+
+    match &args.out {
+        Some(out) => {
+            fs::write(out, content)?;
+            println!("Report written to {}", out);
+        }
+        None if args.readme.is_some() => {}
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Writes the HTML page of the benchmarks recorded for the year of the project
+fn write_html_report(args: &Report) -> Result<(), Box<dyn error::Error>> {
+    let year = DayParts::load()
+        .ok()
+        .map(|dp| dp.year as i32)
+        .or(Config::get().year)
+        .unwrap_or(AOCDate::today().year);
+
+    let runs = bench_history::load(year)?;
+    if runs.is_empty() {
+        return Err("No benchmark recorded yet, run `cargo aoc bench` first".into());
+    }
+
+    let mut days: Vec<_> = runs.iter().map(|run| Day(run.day)).collect();
+    days.sort();
+    days.dedup();
+    titles::fetch(year as u32, &days);
+
+    let out = args.out.as_deref().unwrap_or("target/aoc/report.html");
+    if let Some(dir) = Path::new(out).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(out, report::html(&runs, year as u32))?;
+    println!("Report written to {}", out);
+
+    Ok(())
+}
+
+/// Executes the "badge" subcommand of the app
+pub fn execute_badge(args: &Badge) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+
+    let day_parts = pm.build_project()?;
+    let parts: Vec<_> = day_parts.iter().collect();
+    if parts.is_empty() {
+        return Err("No implementation found".into());
+    }
+
+    let options = RunnerOptions {
+        profile: args.profile.as_ref(),
+        jobs: args.jobs.unwrap_or(1),
+        ..Default::default()
+    };
+    let results = run_results(&pm, day_parts.year, &parts, options)?;
+
+    // A part is solved when it gives an answer, the right one if it is known
+    let answers = Answers::load(day_parts.year as i32)?;
+    let mut solved: Vec<_> = results
+        .iter()
+        .filter(|r| r.status == Status::Ok)
+        .filter(|r| answers.get(r.day, r.part).is_none_or(|a| a == r.answer))
+        .map(|r| (r.day, r.part))
+        .collect();
+    solved.sort();
+    solved.dedup();
+
+    let out = Path::new(&args.out);
+    fs::create_dir_all(out)?;
+
+    let runtime = time_format::format(results::total_time(&results));
+    fs::write(
+        out.join("runtime.svg"),
+        badge::svg("runtime", &runtime, "#007ec6"),
+    )?;
+
+    // Every puzzle has two stars, only the unlocked ones counting while the event goes on
+    let total = calendar::unlocked_days(day_parts.year as i32) as usize * 2;
+    let stars = format!("{}/{}", solved.len(), total);
+    fs::write(
+        out.join("stars.svg"),
+        badge::svg("stars", &stars, badge::stars_color(solved.len(), total)),
+    )?;
+
+    println!(
+        "Badges written to {} (runtime: {}, stars: {})",
+        out.display(),
+        runtime,
+        stars
+    );
+
+    Ok(())
+}
+
+/// Runs the default implementation of a part, and returns its answer
+fn solve(
+    pm: &ProjectManager,
+    day_parts: &DayParts,
+    day: Day,
+    part: Part,
+    input: Option<&str>,
+) -> Result<String, Box<dyn error::Error>> {
+    let year = day_parts.year;
+    let date = AOCDate {
+        day: u32::from(day.0),
+        year: year as i32,
+    };
+
+    let template = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/src/answer.rs.tpl"
+    ));
+
+    // Only the default implementation is submitted, alternatives should give the same answer.
+    let body = day_parts
+        .iter()
+        .find(|dp| dp.day == day && dp.part == part && dp.name.is_none())
+        .or_else(|| day_parts.iter().find(|dp| dp.day == day && dp.part == part))
+        .map(|dp| runner_body(dp, template))
+        .ok_or("No matching day & part found")?;
+
+    if input.is_none() {
+        download_input(date)?;
+    }
+
+    write_autobuild(
+        pm,
+        year,
+        &[day],
+        &body,
+        input,
+        &BuildProfile::Release,
+        false,
+    );
+
+    let output = run_autobuild_captured(pm.quiet, &BuildProfile::Release, &[])?;
+    let answer = results::unescape(output.trim());
+    if answer.is_empty() {
+        return Err("The solver did not produce any answer".into());
+    }
+    Ok(answer)
+}
+
+/// Executes the "answer" subcommand of the app
+pub fn execute_answer(args: &Answer) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+    let day_parts = pm.build_project()?;
+
+    let day = args
+        .day
+        .unwrap_or_else(|| day_parts.last().expect("No implementation found").day);
+    let year = day_parts.year as i32;
+
+    let answer = match &args.answer {
+        Some(answer) => answer.clone(),
+        None => solve(&pm, &day_parts, day, args.part, args.input.as_deref())?,
+    };
+
+    let mut answers = Answers::load(year)?;
+    if let Some(previous) = answers.get(day, args.part).filter(|&a| a != answer) {
+        println!("Replacing the previous answer {}", previous);
+    }
+    answers.insert(day, args.part, &answer);
+    answers.save(year)?;
+    println!(
+        "Stored {} as the answer of day {} part {}",
+        answer, day.0, args.part.0
+    );
+
+    Ok(())
+}
+
+/// Executes the "submit" subcommand of the app
+pub fn execute_submit(args: &Submit) -> Result<(), Box<dyn error::Error>> {
+    if http::offline() {
+        return Err("Can't submit an answer in offline mode".into());
+    }
+    let token = CredentialsManager::new().get_session_token()?;
+
+    let pm = ProjectManager::new()?;
+
+    let day_parts = pm.build_project()?;
+
+    let day = args
+        .day
+        .unwrap_or_else(|| day_parts.last().expect("No implementation found").day);
+    let year = day_parts.year;
+
+    let date = AOCDate {
+        day: u32::from(day.0),
+        year: year as i32,
+    };
+
+    let answer = solve(&pm, &day_parts, day, args.part, args.input.as_deref())?;
+
+    // Rejected answers lock the submissions for a while, don't waste it on a known wrong one
+    if !args.force {
+        let previous = submissions::load(date.year)?;
+        submissions::check(&previous, day, args.part, &answer)
+            .map_err(|e| format!("{}, use --force to submit it anyway", e))?;
+    }
+
+    let account = account(year as i32, &token);
+    println!(
+        "Answer of {} day {} part {}: {}",
+        year, day.0, args.part.0, answer
+    );
+    println!("Account: {}", account);
+
+    if args.dry_run {
+        println!("Dry run, nothing was submitted");
+        return Ok(());
+    }
+    if !args.yes && !confirm("Submit it?")? {
+        println!("Nothing was submitted");
+        return Ok(());
+    }
+
+    println!(
+        "Submitting {} for day {} part {}...",
+        answer, day.0, args.part.0
+    );
+
+    loop {
+        let verdict = submit::submit_answer(date, args.part, &answer, &token)?;
+        match &verdict {
+            Verdict::Correct => println!("That's the right answer!"),
+            Verdict::Incorrect(hint) => println!("That's not the right answer. {}", hint),
+            Verdict::TooRecent(wait) => println!("You gave an answer too recently. {}", wait),
+            Verdict::AlreadySolved => {
+                println!("This part is already solved (or not unlocked yet).")
+            }
+            Verdict::Unknown(message) => println!("Unexpected response:\n{}", message),
+        }
+
+        // The next runs of the part will check it still gives this answer
+        if verdict == Verdict::Correct {
+            let mut answers = Answers::load(date.year)?;
+            answers.insert(day, args.part, &answer);
+            answers.save(date.year)?;
+        }
+
+        let submission = Submission::new(day, args.part, &answer, &verdict);
+        if let Err(e) = submissions::append(date.year, submission) {
+            eprintln!("Couldn't log the submission: {}", e);
+        }
+
+        // Waits for the end of the cooldown, then submits again
+        let wait = match &verdict {
+            Verdict::TooRecent(message) if !args.no_retry => submit::wait_time(message),
+            _ => None,
+        };
+        let Some(wait) = wait else {
+            return Ok(());
+        };
+        // The server rounds the time left, one more second avoids another cooldown
+        let until = chrono::Utc::now() + chrono::Duration::from_std(wait + Duration::from_secs(1))?;
+        calendar::wait_until(until, "Submitting again in")?;
+    }
+}
+
+/// Describes the account of a session token : its name, and the session profile it comes from
+fn account(year: i32, token: &str) -> String {
+    let name = submit::account(AOCDate { day: 1, year }, token)
+        .unwrap_or_else(|| "unknown account, is the session token still valid ?".into());
+    match &Config::get().session_profile {
+        Some(profile) => format!("{} (session profile {})", name, profile),
+        None => name,
+    }
+}
+
+/// Asks a yes or no question, no being the default
+fn confirm(question: &str) -> Result<bool, Box<dyn error::Error>> {
+    if !io::stdin().is_terminal() {
+        Err("Can't ask for a confirmation without a terminal, use --yes to skip it")?
+    }
+
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+
+    Ok(matches!(line.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Executes the "submissions" subcommand of the app
+pub fn execute_submissions(args: &Submissions) -> Result<(), Box<dyn error::Error>> {
+    let year = args
+        .year
+        .or_else(|| DayParts::load().ok().map(|dp| dp.year as i32))
+        .or(Config::get().year)
+        .unwrap_or(AOCDate::today().year);
+
+    let submissions = submissions::load(year)?;
+    if submissions.is_empty() {
+        println!("No answer submitted for {} yet", year);
+    } else {
+        print!("{}", submissions::render(&submissions, args.day));
+    }
+
+    Ok(())
+}
+
+/// Executes the "tui" subcommand of the app
+pub fn execute_tui(args: &Tui) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+
+    let mut dashboard = Dashboard::new(&pm.build_project()?);
+    let exe = std::env::current_exe()?;
+
+    loop {
+        print!("{}", dashboard.render());
+        io::stdout().flush()?;
+
+        // The terminal is only in raw mode while waiting for a key, the commands printing as usual
+        let key = {
+            let _raw = RawMode::enable();
+            tui::read_key(&mut io::stdin())?
+        };
+        let Some(key) = key else {
+            return Ok(());
+        };
+
+        match dashboard.handle(key) {
+            Some(Command::Run(day)) => {
+                // Rebuilds the project, the sources may have changed since the last run
+                let results = pm.build_project().and_then(|day_parts| {
+                    dashboard.set_implemented(&day_parts);
+                    let parts: Vec<_> = day_parts
+                        .iter()
+                        .filter(|dp| day.is_none_or(|d| dp.day == d))
+                        .collect();
+                    if parts.is_empty() {
+                        Err("No matching day & part found")?
+                    }
+                    let options = RunnerOptions {
+                        profile: args.profile.as_ref(),
+                        ..Default::default()
+                    };
+                    run_results(&pm, day_parts.year, &parts, options)
+                });
+                match results {
+                    Ok(results) => dashboard.update(results),
+                    Err(e) => dashboard.set_message(e.to_string()),
+                }
+            }
+            Some(Command::Bench(day)) => {
+                process::Command::new(&exe)
+                    .args(["aoc", "bench", "--day", &day.0.to_string()])
+                    .status()?;
+                print!("\nPress any key to go back to the dashboard");
+                io::stdout().flush()?;
+                let _raw = RawMode::enable();
+                tui::read_key(&mut io::stdin())?;
+            }
+            Some(Command::Quit) => return Ok(()),
+            None => {}
+        }
+    }
+}
+
+/// Executes the "verify" subcommand of the app
+pub fn execute_verify(args: &Verify) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+
+    let day_parts = pm.build_project()?;
+    let year = day_parts.year;
+    let answers = Answers::load(year as i32)?;
+
+    let matching_parts: Vec<_> = day_parts
+        .iter()
+        .filter(|dp| args.day.is_none_or(|d| dp.day == d))
+        .filter(|dp| args.part.is_none_or(|p| dp.part == p))
+        .collect();
+
+    let (verified, missing): (Vec<_>, Vec<_>) = matching_parts
+        .into_iter()
+        .partition(|dp| answers.get(dp.day, dp.part).is_some());
+
+    if verified.is_empty() {
+        return Err(format!(
+            "No stored answer found for the selected parts in {}/{}/answers.json",
+            Config::get().account_dir(),
+            year
+        )
+        .into());
+    }
+
+    let options = RunnerOptions {
+        profile: args.profile.as_ref(),
+        timeout: args.timeout,
+        ..Default::default()
+    };
+    let results = run_results(&pm, year, &verified, options)?;
+
+    let mut over_budget = Vec::new();
+    if let Some(max_part) = args.max_part {
+        for result in results.iter().filter(|r| r.generator + r.runner > max_part) {
+            over_budget.push(format!(
+                "{} took {}, over the budget of {} per part",
+                result.display(),
+                time_format::format(result.generator + result.runner),
+                time_format::format(max_part)
+            ));
+        }
+    }
+    if let Some(max_total) = args.max_total {
+        let total = results::total_time(&results);
+        if total > max_total {
+            over_budget.push(format!(
+                "The total runtime is {}, over the budget of {}",
+                time_format::format(total),
+                time_format::format(max_total)
+            ));
+        }
+    }
+
+    if args.output != OutputFormat::Text {
+        let records: Vec<_> = results
+            .iter()
+            .map(|r| {
+                Record::from_result(r).with_expected(answers.get(r.day, r.part).unwrap_or_default())
+            })
+            .collect();
+        match args.output {
+            OutputFormat::Junit => output::print_junit(&records),
+            OutputFormat::Csv => output::print_csv(&records),
+            OutputFormat::Github => {
+                output::print_github(&format!("AOC {} answers", year), &records)?
+            }
+            _ => output::print_json(&records)?,
+        }
+        for message in &over_budget {
+            eprintln!("{}", message);
+        }
+        if results.iter().any(|r| answers.is_failure(r)) {
+            process::exit(WRONG_ANSWER_EXIT_CODE);
+        }
+        if !over_budget.is_empty() {
+            process::exit(OVER_BUDGET_EXIT_CODE);
+        }
+        return Ok(());
+    }
+
+    let mut failures = 0;
+    for result in &results {
+        let expected = answers.get(result.day, result.part).unwrap_or_default();
+
+        match result.status {
+            Status::Ok if result.answer == expected => {
+                println!(
+                    "{} : {} ({})",
+                    result.display(),
+                    color::paint(color::GREEN, "ok"),
+                    color::time(result.generator + result.runner, 0)
+                );
+            }
+            Status::Ok => {
+                failures += 1;
+                println!(
+                    "{} : {}, expected {} but got {}",
+                    result.display(),
+                    color::paint(color::RED, "FAILED"),
+                    expected,
+                    result.answer
+                );
+            }
+            _ => {
+                failures += 1;
+                println!(
+                    "{} : {}, {}",
+                    result.display(),
+                    color::paint(color::RED, "FAILED"),
+                    result.answer
+                );
+            }
+        }
+    }
+
+    for dp in missing {
+        println!("{} : no stored answer", runner_names(dp).1);
+    }
+
+    println!("\n{} passed, {} failed", results.len() - failures, failures);
+    for message in &over_budget {
+        println!("{}", color::paint(color::RED, message));
+    }
+
+    if failures > 0 {
+        process::exit(WRONG_ANSWER_EXIT_CODE);
+    }
+    if !over_budget.is_empty() {
+        process::exit(OVER_BUDGET_EXIT_CODE);
+    }
+    Ok(())
+}
+
+/// Executes the "test" subcommand of the app
+pub fn execute_test(args: &Test) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+
+    let day_parts = pm.build_project()?;
+    let year = day_parts.year;
+
+    let day = args
+        .day
+        .unwrap_or_else(|| day_parts.last().expect("No implementation found").day);
+
+    let mut examples = examples::load(year as i32, day)?;
+    if examples.is_empty() {
+        // Guesses the example from the description of the puzzle
+        let date = AOCDate {
+            day: u32::from(day.0),
+            year: year as i32,
         };
+        let token = CredentialsManager::new().get_session_token().ok();
+        let description = puzzle::load(date, token.as_deref(), false)?;
+        if examples::save_extracted(year as i32, day, &description)? {
+            println!(
+                "Extracted the example from the puzzle in {}, check it",
+                examples::path(year as i32, day).display()
+            );
+            examples = examples::load(year as i32, day)?;
+        }
+    }
+    if examples.is_empty() {
+        return Err(format!(
+            "No example found for day {}, add some in {}",
+            day.0,
+            examples::path(year as i32, day).display()
+        )
+        .into());
+    }
+
+    let template = result_template(None, 1);
+
+    // Each example is run in its own scope, shadowing the input of the day.
+    // The runner outputs one line per implementation, in the same order as `expected`.
+    let mut body = String::new();
+    let mut expected = Vec::new();
+    for (i, example) in examples.iter().enumerate() {
+        let runners: String = day_parts
+            .iter()
+            .filter(|dp| dp.day == day)
+            .filter(|dp| args.part.is_none_or(|p| dp.part == p))
+            .filter_map(|dp| example.expected(dp.part).map(|answer| (dp, answer)))
+            .map(|(dp, answer)| {
+                expected.push((i + 1, answer));
+                runner_body(dp, &template)
+            })
+            .collect();
+
+        if !runners.is_empty() {
+            body += &format!(
+                "\n    {{\n        let input_day{} = ArcStr::from({:?});\n{}\n    }}",
+                day.0, example.input, runners
+            );
+        }
+    }
+
+    if expected.is_empty() {
+        return Err("No example matches the implemented parts".into());
+    }
+
+    let profile = Config::get().profile(Some(day), args.profile.as_ref());
+    write_autobuild(&pm, year, &[], &body, None, &profile, false);
+
+    let results = PartResult::parse_all(&run_autobuild_captured(pm.quiet, &profile, &[])?)?;
+
+    let mut failures = 0;
+    for (result, &(example, expected)) in results.iter().zip(&expected) {
+        match result.status {
+            Status::Ok if result.answer == expected => {
+                println!(
+                    "{} - example {} : {}",
+                    result.display(),
+                    example,
+                    color::paint(color::GREEN, "ok")
+                );
+            }
+            Status::Ok => {
+                failures += 1;
+                println!(
+                    "{} - example {} : {}, expected {} but got {}",
+                    result.display(),
+                    example,
+                    color::paint(color::RED, "FAILED"),
+                    expected,
+                    result.answer
+                );
+            }
+            _ => {
+                failures += 1;
+                println!(
+                    "{} - example {} : {}, {}",
+                    result.display(),
+                    example,
+                    color::paint(color::RED, "FAILED"),
+                    result.answer
+                );
+            }
+        }
+    }
+
+    // A count mismatch means some examples didn't run to their end
+    for (example, _) in expected.iter().skip(results.len()) {
+        println!(
+            "Example {} : {}, no result",
+            example,
+            color::paint(color::RED, "FAILED")
+        );
+    }
+    let passed = results.len().min(expected.len()) - failures;
+    let failures = failures + results.len().abs_diff(expected.len());
+
+    println!("\n{} passed, {} failed", passed, failures);
+    if failures > 0 {
+        process::exit(WRONG_ANSWER_EXIT_CODE);
+    }
+
+    Ok(())
+}
+
+/// Executes the "watch" subcommand of the app
+pub fn execute_watch(args: &Watch) -> Result<(), Box<dyn error::Error>> {
+    let pm = ProjectManager::new()?;
+    let src_dir = pm
+        .lib_path
+        .as_deref()
+        .map(Path::new)
+        .and_then(|lib_path| lib_path.parent())
+        .unwrap_or(Path::new("src"))
+        .to_path_buf();
+
+    // Each run is done by a new cargo-aoc process, so a failure doesn't stop the watch
+    let mut run_args = vec!["aoc".to_string()];
+    if let Some(day) = args.day {
+        run_args.extend(["--day".to_string(), day.0.to_string()]);
+    }
+    if let Some(part) = args.part {
+        run_args.extend(["--part".to_string(), part.0.to_string()]);
+    }
+    if let Some(input) = &args.input {
+        run_args.extend(["--input".to_string(), input.clone()]);
+    }
+    match &args.profile {
+        Some(BuildProfile::Named(name)) => run_args.push(format!("--profile={}", name)),
+        Some(_) => run_args.push("--profile=".to_string()),
+        None => {}
+    }
+    run_args.extend(features::cargo_args());
+
+    let exe = std::env::current_exe()?;
+    let mut last_run = None;
+
+    loop {
+        let sources = sources_hash(&src_dir)?;
+
+        if last_run != Some(sources) {
+            // Editors save in several writes, or several files at once : the run waits until the
+            // sources stay the same for a while
+            thread::sleep(WATCH_DEBOUNCE);
+            if sources_hash(&src_dir)? != sources {
+                continue;
+            }
+            last_run = Some(sources);
+
+            process::Command::new(&exe).args(&run_args).status()?;
+            println!("Watching {} for changes...", src_dir.display());
+        }
+
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Executes the "whoami" subcommand of the app
+pub fn execute_whoami() -> Result<(), Box<dyn error::Error>> {
+    let token = CredentialsManager::new().get_session_token()?;
+
+    // The pages show the account at the top, or its anonymous id, only to logged in users
+    let page = http::get(&Endpoint::ACCOUNT, &http::url("/"), Some(&token))?.body;
+    if !page.contains("[Log Out]") {
+        return Err(credentials::session_expired_error().into());
+    }
+    let name = submit::account_name(&page).ok_or("Could not find the account on the page")?;
+
+    match &Config::get().session_profile {
+        Some(profile) => println!("{} (session profile {})", name, profile),
+        None => println!("{}", name),
+    }
+    Ok(())
+}
+
+/// The time between two checks of the sources by `watch`
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The time the sources must stay the same before `watch` runs them
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Hashes the sources watched by `watch`, the files of a directory and the Cargo.toml.
+///
+/// Their content is hashed rather than their modification time, so that saving a file without
+/// changing it, or touching it, doesn't run the solutions again.
+fn sources_hash(dir: &Path) -> Result<u64, Box<dyn error::Error>> {
+    let mut files = vec![PathBuf::from("Cargo.toml")];
+    list_files(dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for file in files {
+        // A file removed while listing them is a change like any other
+        file.hash(&mut hasher);
+        fs::read(&file).ok().hash(&mut hasher);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Lists the files in a directory, recursively
+fn list_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            list_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+/// The options of the runner used to get results
+#[derive(Debug, Clone)]
+struct RunnerOptions<'a> {
+    /// An alternate input, when running a single day
+    input: Option<&'a str>,
+    /// The profile given on the command line, otherwise each day is built with its profile
+    profile: Option<&'a BuildProfile>,
+    /// The number of days run in parallel
+    jobs: usize,
+    /// How long to wait for each part
+    timeout: Option<Duration>,
+    /// How many times each part is run
+    runs: usize,
+}
+
+impl Default for RunnerOptions<'_> {
+    fn default() -> Self {
+        RunnerOptions {
+            input: None,
+            profile: None,
+            jobs: 1,
+            timeout: None,
+            runs: 1,
+        }
+    }
+}
+
+/// Gets the template printing the results of an implementation, run `runs` times, waiting for
+/// each run until the timeout
+fn result_template(timeout: Option<Duration>, runs: usize) -> String {
+    let run = match timeout {
+        Some(timeout) => include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/template/src/timeout.rs.tpl"
+        ))
+        .replace("{TIMEOUT_NS}", &timeout.as_nanos().to_string())
+        .replace("{TIMEOUT}", &format!("{:?}", timeout)),
+        None => "            println!(\"{}\", run());".to_string(),
+    };
 
-        body += &template
-            .replace("{DAY}", &day.0.to_string())
-            .replace("{RUNNER_NAME}", &name)
-            .replace("{RUNNER_DISPLAY}", &display);
+    include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/src/result.rs.tpl"
+    ))
+    .replace("{RUN}", &run)
+    .replace("{RUNS}", &runs.max(1).to_string())
+}
+
+/// Runs the given implementations, downloading the missing inputs, and gets their results
+///
+/// Without a profile on the command line, each day is built with its own profile of aoc.toml :
+/// the days sharing a profile are built & run together, once per profile.
+/// The results are sorted, to keep the output deterministic.
+fn run_results(
+    pm: &ProjectManager,
+    year: u32,
+    parts: &[&DayPart],
+    options: RunnerOptions,
+) -> Result<Vec<PartResult>, Box<dyn error::Error>> {
+    let mut profiles: Vec<(BuildProfile, Vec<&DayPart>)> = Vec::new();
+    for &dp in parts {
+        let profile = Config::get().profile(Some(dp.day), options.profile);
+        match profiles.iter_mut().find(|(p, _)| *p == profile) {
+            Some((_, parts)) => parts.push(dp),
+            None => profiles.push((profile, vec![dp])),
+        }
+    }
+
+    let mut results = Vec::new();
+    for (profile, parts) in &profiles {
+        results.extend(run_profile_results(pm, year, parts, &options, profile)?);
+    }
+    results.sort_by(|a, b| (a.day, a.part, &a.name).cmp(&(b.day, b.part, &b.name)));
+
+    Ok(results)
+}
+
+/// Runs implementations built with the same profile, and gets their results
+///
+/// With more than one job, the days are run in parallel by a pool of threads in the runner.
+fn run_profile_results(
+    pm: &ProjectManager,
+    year: u32,
+    parts: &[&DayPart],
+    options: &RunnerOptions,
+    profile: &BuildProfile,
+) -> Result<Vec<PartResult>, Box<dyn error::Error>> {
+    let mut days: Vec<_> = parts.iter().map(|dp| dp.day).collect();
+    days.sort();
+    days.dedup();
+
+    if options.input.is_some() && days.len() > 1 {
+        return Err("An alternate input can only be used with a single day".into());
+    }
+
+    for &day in &days {
+        if Config::get().input(day, options.input).is_none() {
+            download_input(AOCDate {
+                day: u32::from(day.0),
+                year: year as i32,
+            })?;
+        }
+    }
+
+    let template = result_template(options.timeout, options.runs);
+    let body: String = if options.jobs > 1 {
+        let tasks: Vec<_> = days
+            .iter()
+            .map(|&day| {
+                let runners: String = parts
+                    .iter()
+                    .filter(|dp| dp.day == day)
+                    .map(|dp| runner_body(dp, &template))
+                    .collect();
+                format!("\n    Box::new(move || {{{}\n    }})", runners)
+            })
+            .collect();
+
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/template/src/pool.rs.tpl"
+        ))
+        .replace("{TASKS}", &tasks.join(","))
+        .replace("{JOBS}", &options.jobs.to_string())
+    } else {
+        parts.iter().map(|dp| runner_body(dp, &template)).collect()
+    };
+
+    write_autobuild(pm, year, &days, &body, options.input, profile, false);
+
+    // After a timeout, the runner exits to stop the solution, and a new one runs the parts left
+    let mut output = String::new();
+    loop {
+        let done: Vec<_> = PartResult::parse_all(&output)?
+            .iter()
+            .map(PartResult::key)
+            .collect();
+        let lines = run_autobuild_captured(pm.quiet, profile, &done)?;
+        output.push_str(&lines);
+        let results = PartResult::parse_all(&lines)?;
+        if !results.iter().any(|r| r.status == Status::Timeout) {
+            break;
+        }
+    }
+
+    Ok(PartResult::merge_runs(PartResult::parse_all(&output)?))
+}
+
+/// Builds the names used by the generated code & the display for a given runner
+fn runner_names(dp: &DayPart) -> (String, String) {
+    if let Some(n) = &dp.name {
+        (
+            format!("day{}_part{}_{}", dp.day.0, dp.part.0, n.to_lowercase()),
+            format!("Day {} - Part {} - {}", dp.day.0, dp.part.0, n),
+        )
+    } else {
+        (
+            format!("day{}_part{}", dp.day.0, dp.part.0),
+            format!("Day {} - Part {}", dp.day.0, dp.part.0),
+        )
     }
+}
 
-    if body.is_empty() {
-        return Err("No matching day & part found".into());
-    }
+fn runner_body(dp: &DayPart, template: &str) -> String {
+    let (name, display) = runner_names(dp);
+
+    template
+        .replace("{DAY}", &dp.day.0.to_string())
+        .replace("{PART}", &dp.part.0.to_string())
+        .replace("{NAME}", dp.name.as_deref().unwrap_or_default())
+        .replace("{RUNNER_NAME}", &name)
+        .replace("{RUNNER_DISPLAY}", &display)
+}
+
+fn runners_body(
+    day_parts: &DayParts,
+    day: Day,
+    part: Option<Part>,
+    alt: Option<&str>,
+    template: &str,
+) -> String {
+    day_parts
+        .iter()
+        .filter(|dp| dp.day == day)
+        .filter(|dp| {
+            if let Some(p) = part {
+                dp.part == p
+            } else {
+                true
+            }
+        })
+        .filter(|dp| alt.is_none() || dp.name.as_deref() == alt)
+        .map(|dp| runner_body(dp, template))
+        .collect()
+}
 
-    download_input(date)?;
+/// Writes the autobuild crate, used to run solutions, in `target/aoc/aoc-autobuild`
+fn write_autobuild(
+    pm: &ProjectManager,
+    year: u32,
+    days: &[Day],
+    body: &str,
+    input: Option<&str>,
+    profile: &BuildProfile,
+    header: bool,
+) {
+    let cargo_content = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/template/Cargo-run.toml.tpl"
+    ))
+    .replace("{CRATE_NAME}", &pm.name)
+    .replace("{FEATURES}", &features::dependency())
+    .replace("{RUNNER_VERSION}", progress::dependency())
+    .replace("{PROFILE}", &profile.manifest_section());
+
+    // The title of the puzzle follows the year, when running a single day
+    let header = match (header, days) {
+        (false, _) => String::new(),
+        (true, [day]) => match titles::get(year, *day) {
+            Some(title) => format!(
+                "println!(\"AOC {{YEAR}}\");\n    println!(\"{{}}\", {:?});",
+                title
+            ),
+            None => "println!(\"AOC {YEAR}\");".to_string(),
+        },
+        (true, _) => "println!(\"AOC {YEAR}\");".to_string(),
+    };
 
     let main_content = include_str!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/template/src/main.rs.tpl"
     ))
+    .replace("{HEADER}", &header)
     .replace("{CRATE_SLUG}", &pm.slug)
-    .replace("{YEAR}", &day_parts.year.to_string())
-    .replace("{INPUT}", &template_input(day, year, args.input.as_deref()))
-    .replace("{BODY}", &body);
+    .replace("{COLOR}", &color::enabled().to_string())
+    .replace("{FORMAT_TIME}", &time_format::template())
+    .replace("{STACK_SIZE}", &stack_size::template())
+    .replace("{MEMORY}", &memory::template())
+    .replace("{PROGRESS}", &progress::template())
+    .replace("{YEAR}", &year.to_string())
+    .replace(
+        "{INPUT}",
+        &days
+            .iter()
+            .map(|&day| template_input(day, year, input))
+            .collect::<String>(),
+    )
+    .replace("{BODY}", body);
 
     fs::create_dir_all("target/aoc/aoc-autobuild/src")
         .expect("failed to create autobuild directory");
@@ -326,196 +2442,356 @@ pub fn execute_default(args: &Cli) -> Result<(), Box<dyn error::Error>> {
         .expect("failed to write Cargo.toml");
     fs::write("target/aoc/aoc-autobuild/src/main.rs", main_content)
         .expect("failed to write src/main.rs");
+}
 
-    let status = process::Command::new("cargo")
-        .args(["run", "--release"])
+/// Runs the autobuild crate, and returns the result lines of its standard output, without their
+/// marker. Cargo messages (unless quiet), errors & prints of the solutions are still displayed.
+///
+/// The implementations already run, identified by their [`PartResult::key`], are skipped.
+fn run_autobuild_captured(
+    quiet: bool,
+    profile: &BuildProfile,
+    done: &[String],
+) -> Result<String, Box<dyn error::Error>> {
+    let mut command = process::Command::new("cargo");
+    command.arg("run").args(profile.cargo_args("release"));
+    if !done.is_empty() {
+        command.env("CARGO_AOC_DONE", done.join("\n"));
+    }
+    if quiet {
+        command.arg("--quiet").env("CARGO_AOC_NO_SPINNER", "1");
+    }
+    let started = Instant::now();
+    let mut child = command
         .current_dir("target/aoc/aoc-autobuild")
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::inherit())
         .spawn()
-        .expect("Failed to run cargo")
-        .wait()
-        .expect("Failed to wait for cargo");
+        .expect("Failed to run cargo");
+
+    // The lines printed by the solutions are passed on as they come, on the standard error to
+    // keep the standard output machine readable
+    let mut result_lines = String::new();
+    let stdout = child.stdout.take().expect("The standard output is piped");
+    for line in io::BufReader::new(stdout).split(b'\n') {
+        let line = String::from_utf8_lossy(&line?).into_owned();
+        match line.split_once(results::MARKER) {
+            Some((printed, result)) => {
+                if !printed.is_empty() {
+                    eprintln!("{}", printed);
+                }
+                result_lines.push_str(result);
+                result_lines.push('\n');
+            }
+            None => eprintln!("{}", line),
+        }
+    }
+
+    let status = child.wait().expect("Failed to wait for cargo");
+    notify::finished(status.success(), started.elapsed());
 
     if !status.success() {
-        process::exit(status.code().unwrap_or(-1));
+        return Err(format!("cargo run failed with code {}", status.code().unwrap_or(-1)).into());
     }
-    Ok(())
+
+    Ok(result_lines)
 }
 
 pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
+    args.output.check(
+        "bench",
+        &[OutputFormat::Text, OutputFormat::Json, OutputFormat::Csv],
+    )?;
+
+    let backend = args
+        .backend
+        .or(Config::get().bench.backend)
+        .unwrap_or_default();
+    if backend != BenchBackend::Criterion {
+        // Divan & iai-callgrind print their numbers, without storing them
+        args.output
+            .check("the divan & iai-callgrind backends", &[OutputFormat::Text])?;
+        if args.open {
+            return Err("The benchmark reports are only made by the criterion backend".into());
+        }
+    }
+    backend.check()?;
+    if args.flamegraph {
+        if backend != BenchBackend::Criterion || cfg!(windows) {
+            return Err("Flamegraphs are made by pprof with the criterion backend, on Unix".into());
+        }
+        // Criterion only profiles the benchmarks then, without measuring them
+        args.output
+            .check("bench --flamegraph", &[OutputFormat::Text])?;
+    }
+    let criterion_settings = args.criterion.or(&Config::get().bench.criterion);
+    let criterion_only = args.criterion != criterion::Settings::default()
+        || args.save_baseline.is_some()
+        || args.baseline.is_some()
+        || args.check_regressions.is_some();
+    if backend != BenchBackend::Criterion && criterion_only {
+        return Err("The settings of criterion are only used by the criterion backend".into());
+    }
+    let criterion_config = criterion_settings.expression(args.flamegraph)?;
+    let environment = args.environment.or(&Config::get().bench.environment);
+    if environment.cooldown.is_some() && backend != BenchBackend::Criterion {
+        return Err("The pauses between the parts are made by the criterion harness".into());
+    }
+    let pin_args = environment.cargo_args()?;
+    if args.compare_ref.is_some() {
+        if backend != BenchBackend::Criterion {
+            return Err("--compare-ref compares the results of the criterion backend".into());
+        }
+        args.output
+            .check("bench --compare-ref", &[OutputFormat::Text])?;
+    }
+    if args.alts {
+        if backend != BenchBackend::Criterion {
+            return Err("--alts ranks the results of the criterion backend".into());
+        }
+        args.output.check("bench --alts", &[OutputFormat::Text])?;
+    }
+    if args.memory {
+        // The counts are printed after the benchmarks, in their own table
+        args.output.check("bench --memory", &[OutputFormat::Text])?;
+        memory::init(true);
+    }
+    // Checked out first, to fail before benchmarking if the revision is unknown
+    let worktree = args.compare_ref.as_deref().map(Worktree::add).transpose()?;
+
     let day: Option<Day> = args.day;
     let part: Option<Part> = args.part;
+    let input = args.input.as_deref();
 
     let pm = ProjectManager::new()?;
 
     let day_parts = pm.build_project()?;
 
-    let day = day.unwrap_or_else(|| day_parts.last().expect("No implementation found").day);
+    // Every implemented day with --all, the last one by default
+    // A harness written in the project covers every day, unless one is given
+    let days: Vec<Day> = if args.all || (args.write_harness && day.is_none()) {
+        let mut days: Vec<_> = day_parts.iter().map(|dp| dp.day).collect();
+        days.sort();
+        days.dedup();
+        days
+    } else {
+        vec![day.unwrap_or_else(|| day_parts.last().expect("No implementation found").day)]
+    };
     let year = day_parts.year;
+    titles::fetch(year, &days);
+    // The inputs are included from benches/aoc.rs in the project, or from the generated crate
+    let input_root = if args.write_harness {
+        ".."
+    } else {
+        "../../../.."
+    };
+    // The debug info names the inlined functions in the flamegraphs, and the settings of a day
+    // are only used when benchmarking it alone
+    let profile = match &args.profile {
+        None if args.flamegraph => BuildProfile::Profiling,
+        profile => {
+            Config::get().profile(Some(days[0]).filter(|_| days.len() == 1), profile.as_ref())
+        }
+    };
+
+    let mut dependencies = backend.dependency();
+    if args.flamegraph {
+        dependencies.push('\n');
+        dependencies.push_str(bench_backend::PPROF_DEPENDENCY);
+    }
 
     let cargo_content = include_str!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/template/Cargo-bench.toml.tpl"
     ))
     .replace("{CRATE_NAME}", &pm.name)
-    .replace(
-        "{PROFILE}",
-        if args.profile {
-            "[profile.release]\ndebug = true"
-        } else {
-            ""
-        },
-    );
-
-    let bench_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/aoc_benchmark.rs.tpl"
-    ));
-
-    let part_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/part.rs.tpl"
-    ));
-
-    let gen_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/gen.rs.tpl"
-    ));
-
-    let impl_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/impl.rs.tpl"
-    ));
-
-    let gen_impl_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/gen_impl.rs.tpl"
-    ));
-
-    let matching_parts = day_parts.iter().filter(|dp| dp.day == day).filter(|dp| {
-        if let Some(p) = part {
-            dp.part == p
-        } else {
-            true
-        }
-    });
-
-    let mut parts: Vec<_> = matching_parts.clone().map(|dp| dp.part).collect();
+    .replace("{FEATURES}", &features::dependency())
+    .replace("{BENCH_DEPENDENCY}", &dependencies)
+    .replace("{RUNNER_VERSION}", progress::dependency())
+    .replace("{PROFILE}", &profile.manifest_section());
+
+    let Templates {
+        bench: bench_tpl,
+        day: day_tpl,
+        part: part_tpl,
+        gen: gen_tpl,
+        runner: impl_tpl,
+        gen_runner: gen_impl_tpl,
+    } = backend.templates();
+
+    let matching_parts = day_parts
+        .iter()
+        .filter(|dp| days.contains(&dp.day))
+        .filter(|dp| {
+            if let Some(p) = part {
+                dp.part == p
+            } else {
+                true
+            }
+        })
+        .filter(|dp| {
+            !args.alts
+                || day_parts
+                    .iter()
+                    .filter(|other| (other.day, other.part) == (dp.day, dp.part))
+                    .count()
+                    > 1
+        });
+
+    let mut parts: Vec<_> = matching_parts.clone().map(|dp| (dp.day, dp.part)).collect();
     parts.sort();
     parts.dedup();
 
-    let body: String = parts
-        .into_iter()
-        .map(|p| {
-            let part_name = format!("day{}_part{}", day.0, p.0);
-            part_tpl
-                .replace("{PART_NAME}", &part_name)
-                .replace("{DAY}", &day.0.to_string())
-                .replace("{PART}", &p.0.to_string())
-                .replace(
-                    "{IMPLS}",
-                    &matching_parts
-                        .clone()
-                        .filter(|dp| dp.part == p)
-                        .map(|dp| {
-                            impl_tpl
-                                .replace(
-                                    "{RUNNER_NAME}",
-                                    &if let Some(n) = &dp.name {
-                                        format!(
-                                            "day{}_part{}_{}",
-                                            dp.day.0,
-                                            dp.part.0,
-                                            n.to_lowercase()
+    let generators = args.generator || Config::get().bench.generator;
+
+    // The names of the benchmark functions, for the harnesses listing them
+    let mut functions: Vec<_> = parts
+        .iter()
+        .map(|(day, p)| format!("day{}_part{}", day.0, p.0))
+        .collect();
+    if generators {
+        functions.extend(
+            parts
+                .iter()
+                .map(|(day, p)| format!("day{}_part{}_generator", day.0, p.0)),
+        );
+    }
+
+    // The parts of each day, in the group of the day
+    let body = days
+        .iter()
+        .map(|&day| {
+            let group = &titles::bench_groups(year, day)[0];
+            let parts = parts
+                .iter()
+                .filter(|(d, _)| *d == day)
+                .map(|&(day, p)| {
+                    let part_name = format!("day{}_part{}", day.0, p.0);
+                    let part_group = format!("{}/part{}", group, p.0);
+                    part_tpl
+                        .replace("{PART_NAME}", &part_name)
+                        .replace("{GROUP}", &format!("{:?}", part_group))
+                        .replace("{DAY}", &day.0.to_string())
+                        .replace("{PART}", &p.0.to_string())
+                        .replace(
+                            "{IMPLS}",
+                            &matching_parts
+                                .clone()
+                                .filter(|dp| dp.day == day && dp.part == p)
+                                .map(|dp| {
+                                    impl_tpl
+                                        .replace("{RUNNER_NAME}", &runner_names(dp).0)
+                                        .replace("{DAY}", &dp.day.0.to_string())
+                                        .replace(
+                                            "{BENCH_ID}",
+                                            &criterion::bench_id(dp.part, dp.name.as_deref()),
                                         )
-                                    } else {
-                                        format!("day{}_part{}", dp.day.0, dp.part.0)
-                                    },
-                                )
-                                .replace("{DAY}", &dp.day.0.to_string())
-                                .replace(
-                                    "{NAME}",
-                                    if let Some(n) = &dp.name {
-                                        n
-                                    } else {
-                                        "(default)"
-                                    },
-                                )
-                                .replace("{PART_NAME}", &part_name)
-                        })
-                        .collect::<String>(),
-                )
+                                        .replace(
+                                            "{NAME}",
+                                            dp.name.as_deref().unwrap_or("(default)"),
+                                        )
+                                        .replace("{PART_NAME}", &part_name)
+                                        .replace(
+                                            "{INPUTS}",
+                                            &input_code(day, year, input, input_root),
+                                        )
+                                })
+                                .collect::<String>(),
+                        )
+                })
+                .collect::<Vec<_>>()
+                .join(&environment.cooldown_code());
+            (group, parts)
         })
-        .collect();
+        .filter(|(_, parts)| !parts.is_empty())
+        .map(|(group, parts)| {
+            day_tpl
+                .replace("{GROUP}", &format!("{:?}", group))
+                .replace("{PARTS}", &parts)
+        })
+        .collect::<Vec<_>>()
+        .join(&environment.cooldown_code());
 
+    if body.is_empty() && args.alts {
+        return Err("No matching part has alternative implementations".into());
+    }
     if body.is_empty() {
         return Err("No matching day & part found".into());
     }
 
-    let gens = if args.generator {
-        let mut parts: Vec<_> = matching_parts.clone().map(|dp| dp.part).collect();
-        parts.sort();
-        parts.dedup();
-
-        parts
-            .into_iter()
-            .map(|p| {
+    let gens = if generators {
+        days.iter()
+            .map(|&day| {
                 let gen_name = format!("day{}", day.0);
-                gen_tpl
-                    .replace("{GEN_NAME}", &gen_name)
-                    .replace("{DAY}", &day.0.to_string())
-                    .replace("{PART}", &p.0.to_string())
-                    .replace(
-                        "{IMPLS}",
-                        &matching_parts
-                            .clone()
-                            .filter(|dp| dp.part == p)
-                            .map(|dp| {
-                                gen_impl_tpl
-                                    .replace(
-                                        "{RUNNER_NAME}",
-                                        &if let Some(n) = &dp.name {
-                                            format!(
-                                                "day{}_part{}_{}",
-                                                dp.day.0,
-                                                dp.part.0,
-                                                n.to_lowercase()
+                let group = format!("Generator {}", titles::bench_groups(year, day)[0]);
+                let gens: String = parts
+                    .iter()
+                    .filter(|(d, _)| *d == day)
+                    .map(|&(day, p)| {
+                        let part_group = format!("{}/part{}", group, p.0);
+                        gen_tpl
+                            .replace("{GEN_NAME}", &gen_name)
+                            .replace("{GROUP}", &format!("{:?}", part_group))
+                            .replace("{DAY}", &day.0.to_string())
+                            .replace("{PART}", &p.0.to_string())
+                            .replace(
+                                "{IMPLS}",
+                                &matching_parts
+                                    .clone()
+                                    .filter(|dp| dp.day == day && dp.part == p)
+                                    .map(|dp| {
+                                        gen_impl_tpl
+                                            .replace("{RUNNER_NAME}", &runner_names(dp).0)
+                                            .replace("{DAY}", &dp.day.0.to_string())
+                                            .replace(
+                                                "{BENCH_ID}",
+                                                &criterion::bench_id(dp.part, dp.name.as_deref()),
+                                            )
+                                            .replace(
+                                                "{NAME}",
+                                                dp.name.as_deref().unwrap_or("(default)"),
+                                            )
+                                            .replace("{GEN_NAME}", &gen_name)
+                                            .replace(
+                                                "{INPUTS}",
+                                                &input_code(day, year, input, input_root),
                                             )
-                                        } else {
-                                            format!("day{}_part{}", dp.day.0, dp.part.0)
-                                        },
-                                    )
-                                    .replace("{DAY}", &dp.day.0.to_string())
-                                    .replace(
-                                        "{NAME}",
-                                        if let Some(n) = &dp.name {
-                                            n
-                                        } else {
-                                            "(default)"
-                                        },
-                                    )
-                                    .replace("{GEN_NAME}", &gen_name)
-                            })
-                            .collect::<String>(),
-                    )
+                                    })
+                                    .collect::<String>(),
+                            )
+                    })
+                    .collect();
+                (group, gens)
+            })
+            .filter(|(_, gens)| !gens.is_empty())
+            .map(|(group, gens)| {
+                day_tpl
+                    .replace("{GROUP}", &format!("{:?}", group))
+                    .replace("{PARTS}", &gens)
             })
             .collect()
     } else {
         String::new()
     };
 
-    let date = AOCDate {
-        day: u32::from(day.0),
-        year: year as i32,
-    };
-    download_input(date)?;
+    for &day in &days {
+        if Config::get().input(day, input).is_none() {
+            download_input(AOCDate {
+                day: u32::from(day.0),
+                year: year as i32,
+            })?;
+        }
+    }
 
     let main_content = bench_tpl
         .replace("{CRATE_SLUG}", &pm.slug)
         .replace("{PARTS}", &body)
         .replace("{GENS}", &gens)
+        .replace("{FUNCTIONS}", &functions.join(", "))
+        .replace("{CONFIG}", &criterion_config)
         .replace(
             "{BENCHMARKS}",
-            if args.generator {
+            if generators {
                 "aoc_benchmark, input_benchmark"
             } else {
                 "aoc_benchmark"
@@ -523,9 +2799,16 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
         )
         .replace(
             "{INPUTS}",
-            &template_input(day, year, args.input.as_deref()),
+            &days
+                .iter()
+                .map(|&day| input_code(day, year, input, input_root))
+                .collect::<String>(),
         );
 
+    if args.write_harness {
+        return write_harness(&main_content, &dependencies);
+    }
+
     fs::create_dir_all("target/aoc/aoc-autobench/benches")
         .expect("failed to create autobench directory");
     fs::write("target/aoc/aoc-autobench/Cargo.toml", cargo_content)
@@ -536,19 +2819,124 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
     )
     .expect("failed to write src/aoc_benchmark.rs");
 
-    let status = process::Command::new("cargo")
-        .args(["bench"])
-        .current_dir("target/aoc/aoc-autobench")
+    // Only the benchmark crate (and the solutions it builds) is built for this CPU
+    let native_cpu = args.native_cpu || Config::get().bench.native_cpu;
+    let cargo_bench = || {
+        let mut command = process::Command::new("cargo");
+        command
+            .args(&pin_args)
+            .arg("bench")
+            .args(profile.cargo_args("bench"))
+            .current_dir("target/aoc/aoc-autobench");
+        if native_cpu {
+            command.env("RUSTFLAGS", bench_env::native_rustflags());
+        }
+        command
+    };
+
+    if let Some(warmup) = environment.warmup {
+        // Built first, the compilation heating the CPU up too
+        let status = cargo_bench().arg("--no-run").status()?;
+        if !status.success() {
+            drop(worktree);
+            process::exit(status.code().unwrap_or(-1));
+        }
+        eprintln!("Warming the CPU up for {}", time_format::format(warmup));
+        bench_env::warm_up(warmup);
+    }
+
+    let mut command = cargo_bench();
+    if args.flamegraph {
+        command.args(["--", "--profile-time", "5"]);
+    } else if let Some(name) = &args.save_baseline {
+        command.args(["--", "--save-baseline", name]);
+    } else if let Some(name) = &args.baseline {
+        command.args(["--", "--baseline", name]);
+    }
+    if args.output != OutputFormat::Text {
+        // Keeps the standard output for the results
+        command.stdout(io::stderr());
+    }
+
+    let status = command
         .spawn()
         .expect("Failed to run cargo")
         .wait()
         .expect("Failed to wait for cargo");
 
     if !status.success() {
+        drop(worktree);
         process::exit(status.code().unwrap_or(-1));
     }
 
-    if args.open {
+    if args.flamegraph {
+        return copy_flamegraphs(year, matching_parts);
+    }
+
+    let mut regressions = Vec::new();
+    if backend == BenchBackend::Criterion {
+        let criterion_dir = Path::new("target/aoc/aoc-autobench/target/criterion");
+        let mut records = Vec::new();
+        for dp in matching_parts.clone() {
+            let id = criterion::bench_id(dp.part, dp.name.as_deref());
+            let groups = titles::bench_groups(year, dp.day);
+            let estimates = Estimates::load_first(criterion_dir, &groups, &id)?;
+            records.push(BenchRecord::new(dp, "runner", &estimates));
+
+            if generators {
+                let groups: Vec<_> = groups.iter().map(|g| format!("Generator {}", g)).collect();
+                let estimates = Estimates::load_first(criterion_dir, &groups, &id)?;
+                records.push(BenchRecord::new(dp, "generator", &estimates));
+            }
+        }
+
+        // Every run is kept, the next ones being compared to the best of them
+        if let Some(threshold) = args.check_regressions {
+            let runs = bench_history::load(year as i32)?;
+            regressions = bench_history::regressions(&runs, &records, threshold);
+        }
+        bench_history::append(year as i32, &records)?;
+
+        match args.output {
+            OutputFormat::Csv => output::print_csv(&records),
+            OutputFormat::Json => output::print_json(&records)?,
+            _ if args.all || generators => print_bench_summary(&records),
+            _ => {}
+        }
+        if args.alts {
+            print_alt_ranking(&records);
+        }
+    }
+
+    if let Some(worktree) = worktree {
+        let date = AOCDate {
+            day: u32::from(days[0].0),
+            year: year as i32,
+        };
+        let mut settings = criterion_settings.args();
+        settings.extend(environment.args());
+        return compare_to_ref(args, &worktree, date, &settings, matching_parts);
+    }
+
+    if args.memory || args.alts {
+        // Counted & checked by the runner, a single run being enough
+        let parts: Vec<_> = matching_parts.collect();
+        let options = RunnerOptions {
+            input,
+            profile: Some(&profile),
+            ..Default::default()
+        };
+        let results = run_results(&pm, year, &parts, options)?;
+        if args.memory {
+            println!("\n{}", color::paint(color::BOLD, "Memory"));
+            results::print_memory(&results);
+        }
+        if args.alts {
+            warn_different_answers(&results);
+        }
+    }
+
+    if backend == BenchBackend::Criterion && (args.open || Config::get().bench.open) {
         let index = "target/aoc/aoc-autobench/target/criterion/report/index.html";
 
         if !Path::new(index).exists() {
@@ -557,24 +2945,459 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
         webbrowser::open(index)?;
     }
 
+    if !regressions.is_empty() {
+        for regression in &regressions {
+            eprintln!("{}", color::paint(color::RED, &regression.to_string()));
+        }
+        return Err(format!("{} implementation(s) got slower", regressions.len()).into());
+    }
+
     Ok(())
 }
 
-fn template_input(day: Day, year: u32, input: Option<&str>) -> String {
-    let day = day.0.to_string();
-    let path = input
-        .map(|p| {
-            if Path::new(p).is_relative() {
-                format!("../../../../{}", p)
-            } else {
-                p.to_string()
+/// Writes the benchmark harness to benches/aoc.rs, and declares it in the Cargo.toml of the
+/// project, so that `cargo bench` runs it without cargo-aoc.
+///
+/// The manifest is edited as text, to keep its comments and formatting, and only when the
+/// benchmark or its dependencies are missing, so that running it again changes nothing. The
+/// edited manifest is checked before being written, the lines to add being printed instead when
+/// its layout can't be edited this way.
+fn write_harness(content: &str, dependency: &str) -> Result<(), Box<dyn error::Error>> {
+    fs::create_dir_all("benches")?;
+    fs::write(
+        "benches/aoc.rs",
+        format!(
+            "// Generated by `cargo aoc bench --write-harness`, run it again instead of editing \
+             this file\n{}",
+            content
+        ),
+    )?;
+    println!("Wrote the benchmarks to benches/aoc.rs");
+
+    let mut manifest = fs::read_to_string("Cargo.toml")?;
+    let cargo: toml::Table = manifest.parse()?;
+    let missing: Vec<_> = dependency
+        .lines()
+        .filter(|line| !has_dependency(&cargo, dependency_name(line)))
+        .collect();
+    let declared = aoc_benches(&cargo) > 0;
+    if declared && missing.is_empty() {
+        return Ok(());
+    }
+
+    if !missing.is_empty() {
+        // The dependencies follow the header of the table, when it has one
+        let header = manifest
+            .split_inclusive('\n')
+            .scan(0, |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+                Some((start, line))
+            })
+            .find(|(_, line)| is_table_header(line, "dev-dependencies"));
+        match header {
+            Some((start, line)) => manifest.insert_str(
+                start + line.trim_end().len(),
+                &format!("\n{}", missing.join("\n")),
+            ),
+            None => {
+                manifest = format!(
+                    "{}\n\n[dev-dependencies]\n{}\n",
+                    manifest.trim_end(),
+                    missing.join("\n")
+                )
             }
+        }
+    }
+    if !declared {
+        manifest = format!(
+            "{}\n\n[[bench]]\nname = \"aoc\"\nharness = false\n",
+            manifest.trim_end()
+        );
+    }
+
+    let edited = manifest.parse::<toml::Table>().ok().filter(|edited| {
+        aoc_benches(edited) == 1
+            && dependency
+                .lines()
+                .all(|line| has_dependency(edited, dependency_name(line)))
+    });
+    if edited.is_none() {
+        let mut lines = String::new();
+        if !missing.is_empty() {
+            lines += &format!("\n[dev-dependencies]\n{}\n", missing.join("\n"));
+        }
+        if !declared {
+            lines += "\n[[bench]]\nname = \"aoc\"\nharness = false\n";
+        }
+        return Err(format!(
+            "Could not edit Cargo.toml, add these lines to it to run the benchmarks:\n{}",
+            lines
+        )
+        .into());
+    }
+
+    fs::write("Cargo.toml", manifest)?;
+    println!("Declared the aoc benchmark in Cargo.toml, run it with `cargo bench --bench aoc`");
+
+    Ok(())
+}
+
+/// Gets the name of the crate of a dependency line, like `criterion = "0.5"`
+fn dependency_name(line: &str) -> &str {
+    line.split('=').next().unwrap_or_default().trim()
+}
+
+/// Tells if a manifest has a dependency the benchmarks can use
+fn has_dependency(cargo: &toml::Table, name: &str) -> bool {
+    ["dev-dependencies", "dependencies"]
+        .iter()
+        .any(|table| cargo.get(*table).and_then(|deps| deps.get(name)).is_some())
+}
+
+/// Counts the benchmarks named `aoc` declared in a manifest
+fn aoc_benches(cargo: &toml::Table) -> usize {
+    cargo
+        .get("bench")
+        .and_then(|benches| benches.as_array())
+        .map_or(0, |benches| {
+            benches
+                .iter()
+                .filter(|bench| bench.get("name").and_then(|n| n.as_str()) == Some("aoc"))
+                .count()
         })
-        .unwrap_or_else(|| format!("../../../../input/{}/day{}.txt", year, &day));
+}
+
+/// Tells if a line of a manifest is the header of a table, like `[dev-dependencies]`, the
+/// headers of its sub-tables or of other tables ending the same way not matching
+fn is_table_header(line: &str, table: &str) -> bool {
+    let line = line.split('#').next().unwrap_or_default();
+    let header: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    header == format!("[{}]", table)
+}
+
+/// Prints the means of every implementation benchmarked, its generator apart from its runner,
+/// and the total of the fastest implementation of each part
+fn print_bench_summary(records: &[BenchRecord]) {
+    // The generator & runner means of each implementation, the default one first
+    let mut means: BTreeMap<(u8, u8, Option<&str>), (Option<f64>, Option<f64>)> = BTreeMap::new();
+    for record in records {
+        let entry = means
+            .entry((record.day, record.part, record.alt.as_deref()))
+            .or_default();
+        if record.target == "generator" {
+            entry.0 = Some(record.mean_ns);
+        } else {
+            entry.1 = Some(record.mean_ns);
+        }
+    }
+
+    let name_width = means
+        .keys()
+        .map(|(_, _, alt)| alt.unwrap_or("(default)").len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let time = |mean: Option<f64>| match mean {
+        Some(ns) => color::time(Duration::from_nanos(ns as u64), 12),
+        None => format!("{:>12}", "-"),
+    };
+
+    println!("\n{}", color::paint(color::BOLD, "Summary"));
+    println!(
+        "{:>3} {:>4}  {:<name_width$}  {:>12}  {:>12}",
+        "Day", "Part", "Name", "Generator", "Runner"
+    );
+    let mut fastest: BTreeMap<(u8, u8), f64> = BTreeMap::new();
+    for (&(day, part, alt), &(generator, runner)) in &means {
+        println!(
+            "{:>3} {:>4}  {:<name_width$}  {}  {}",
+            day,
+            part,
+            alt.unwrap_or("(default)"),
+            time(generator),
+            time(runner)
+        );
+        let part = fastest.entry((day, part)).or_insert(f64::INFINITY);
+        *part = part.min(generator.unwrap_or(0.) + runner.unwrap_or(0.));
+    }
+
+    let total = Duration::from_nanos(fastest.values().sum::<f64>() as u64);
+    println!(
+        "\nTotal, with the fastest implementation of each part : {}",
+        color::paint(color::BOLD, &time_format::format(total))
+    );
+}
+
+/// Prints the implementations of each part from the fastest to the slowest, with how many
+/// times slower than the fastest they are
+fn print_alt_ranking(records: &[BenchRecord]) {
+    let mut parts: BTreeMap<(u8, u8), Vec<&BenchRecord>> = BTreeMap::new();
+    for record in records.iter().filter(|r| r.target == "runner") {
+        parts
+            .entry((record.day, record.part))
+            .or_default()
+            .push(record);
+    }
+
+    for ((day, part), mut implementations) in parts {
+        implementations.sort_by(|a, b| a.mean_ns.total_cmp(&b.mean_ns));
+        let fastest = implementations[0].mean_ns;
+        let width = implementations
+            .iter()
+            .map(|r| r.alt.as_deref().unwrap_or("(default)").len())
+            .max()
+            .unwrap_or(0);
+
+        println!(
+            "\n{}",
+            color::paint(color::BOLD, &format!("Day {} - Part {}", day, part))
+        );
+        for (rank, record) in implementations.iter().enumerate() {
+            let ratio = format!("x{:.2}", record.mean_ns / fastest);
+            println!(
+                "{:>3}. {:<width$}  {}  {:>8}",
+                rank + 1,
+                record.alt.as_deref().unwrap_or("(default)"),
+                color::time(Duration::from_nanos(record.mean_ns as u64), 12),
+                ratio
+            );
+        }
+    }
+}
+
+/// Warns about the parts whose implementations don't all give the same answer
+fn warn_different_answers(results: &[PartResult]) {
+    let mut answers: BTreeMap<(Day, Part), Vec<&PartResult>> = BTreeMap::new();
+    for result in results.iter().filter(|r| r.status == Status::Ok) {
+        answers
+            .entry((result.day, result.part))
+            .or_default()
+            .push(result);
+    }
+
+    for ((day, part), implementations) in answers {
+        if implementations
+            .iter()
+            .all(|r| r.answer == implementations[0].answer)
+        {
+            continue;
+        }
+        let answers: Vec<_> = implementations
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} = {}",
+                    r.name.as_deref().unwrap_or("(default)"),
+                    r.answer
+                )
+            })
+            .collect();
+        eprintln!(
+            "{}",
+            color::paint(
+                color::YELLOW,
+                &format!(
+                    "Day {} - Part {} : the implementations give different answers : {}",
+                    day.0,
+                    part.0,
+                    answers.join(", ")
+                )
+            )
+        );
+    }
+}
+
+/// Benchmarks the same parts at another revision, in its worktree, and prints the changes
+fn compare_to_ref<'a>(
+    args: &Bench,
+    worktree: &Worktree,
+    date: AOCDate,
+    settings: &[String],
+    day_parts: impl Iterator<Item = &'a DayPart> + Clone,
+) -> Result<(), Box<dyn error::Error>> {
+    let reference = args.compare_ref.as_deref().unwrap_or_default();
+    let day = Day(date.day as u8);
+
+    // The inputs aren't committed, the one used here is given to the other revision
+    let input = match Config::get().input(day, args.input.as_deref()) {
+        Some(input) => input.to_string(),
+        None => date.filename(),
+    };
+    let input = env::current_dir()?.join(input);
+
+    let mut command = process::Command::new(env::current_exe()?);
+    command
+        .args(["aoc", "bench", "--backend", "criterion"])
+        .args(["--day", &date.day.to_string()])
+        .arg("--input")
+        .arg(&input)
+        .args(settings)
+        .args(features::cargo_args());
+    if let Some(part) = args.part {
+        command.args(["--part", &part.0.to_string()]);
+    }
+    if args.native_cpu || Config::get().bench.native_cpu {
+        command.arg("--native-cpu");
+    }
+    if args.generator || Config::get().bench.generator {
+        command.arg("--generator");
+    }
+
+    println!("\nBenchmarking {}", reference);
+    let status = command.current_dir(worktree.project_dir()).status()?;
+    if !status.success() {
+        return Err(format!("The benchmarks of {} failed", reference).into());
+    }
+
+    let current_dir = Path::new("target/aoc/aoc-autobench/target/criterion");
+    let reference_dir = worktree
+        .project_dir()
+        .join("target/aoc/aoc-autobench/target/criterion");
+    let generators = args.generator || Config::get().bench.generator;
+
+    let mut rows = Vec::new();
+    for dp in day_parts {
+        let id = criterion::bench_id(dp.part, dp.name.as_deref());
+        let groups = titles::bench_groups(date.year as u32, dp.day);
+        let (_, display_name) = runner_names(dp);
+        rows.push((
+            display_name.clone(),
+            Estimates::load_first(&reference_dir, &groups, &id)?,
+            Estimates::load_first(current_dir, &groups, &id)?,
+        ));
+
+        if generators {
+            let groups: Vec<_> = groups.iter().map(|g| format!("Generator {}", g)).collect();
+            rows.push((
+                format!("{} - Generator", display_name),
+                Estimates::load_first(&reference_dir, &groups, &id)?,
+                Estimates::load_first(current_dir, &groups, &id)?,
+            ));
+        }
+    }
+
+    let width = rows
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    println!(
+        "\n{:width$}   {:>12}   {:>12}   {:>8}",
+        "", reference, "current", "change"
+    );
+    for (name, before, after) in rows {
+        let before = before.mean.point_estimate;
+        let after = after.mean.point_estimate;
+        let change = (after - before) / before * 100.;
+        let style = if change < 0. {
+            color::GREEN
+        } else {
+            color::RED
+        };
+        println!(
+            "{:width$}   {:>12}   {:>12}   {}",
+            name,
+            time_format::format(Duration::from_nanos(before as u64)),
+            time_format::format(Duration::from_nanos(after as u64)),
+            color::paint(style, &format!("{:>+7.1}%", change)),
+        );
+    }
+
+    Ok(())
+}
+
+/// Copies the flamegraphs written by pprof to `target/aoc/flamegraphs`, one per implementation
+fn copy_flamegraphs<'a>(
+    year: u32,
+    day_parts: impl Iterator<Item = &'a DayPart>,
+) -> Result<(), Box<dyn error::Error>> {
+    let criterion_dir = Path::new("target/aoc/aoc-autobench/target/criterion");
+    fs::create_dir_all("target/aoc/flamegraphs")?;
+
+    for dp in day_parts {
+        let id = criterion::bench_id(dp.part, dp.name.as_deref());
+        let group = &titles::bench_groups(year, dp.day)[0];
+        let source = criterion::flamegraph(criterion_dir, group, &id);
+
+        let (runner_name, display_name) = runner_names(dp);
+        let destination = format!("target/aoc/flamegraphs/{}.svg", runner_name);
+        fs::copy(&source, &destination)
+            .map_err(|e| format!("Could not copy {}: {}", source.display(), e))?;
+        println!("{} : {}", display_name, destination);
+    }
+
+    Ok(())
+}
+
+/// Saves the standard input in `target/aoc/stdin.txt`, and gets its absolute path
+fn save_stdin_input() -> Result<String, Box<dyn error::Error>> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    if input.is_empty() {
+        return Err("The standard input is empty".into());
+    }
+
+    fs::create_dir_all("target/aoc")?;
+    let path = env::current_dir()?.join("target/aoc/stdin.txt");
+    fs::write(&path, input)?;
+
+    Ok(path.display().to_string())
+}
+
+fn template_input(day: Day, year: u32, input: Option<&str>) -> String {
+    input_code(day, year, input, "../../../..")
+}
+
+/// Gets the code including the input of a day, in a file of a directory where the project is
+/// found at `root`
+fn input_code(day: Day, year: u32, input: Option<&str>, root: &str) -> String {
+    let file = Config::get()
+        .input(day, input)
+        .map(String::from)
+        .unwrap_or_else(|| {
+            AOCDate {
+                day: u32::from(day.0),
+                year: year as i32,
+            }
+            .filename()
+        });
+    let path = if Path::new(&file).is_relative() {
+        format!("{}/{}", root, file)
+    } else {
+        file
+    };
+    let day = day.0.to_string();
     include_str!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/template/input.rs.tpl"
     ))
-    .replace("{PATH}", &path)
+    .replace("{PATH}", &path.replace('\\', "\\\\"))
     .replace("{DAY}", &day)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_hash_follows_the_content() {
+        let dir = env::temp_dir().join(format!("cargo-aoc-sources-{}", process::id()));
+        fs::create_dir_all(dir.join("day1")).unwrap();
+        fs::write(dir.join("lib.rs"), "mod day1;").unwrap();
+        fs::write(dir.join("day1").join("mod.rs"), "fn part1() {}").unwrap();
+
+        let hash = sources_hash(&dir).unwrap();
+        fs::write(dir.join("lib.rs"), "mod day1;").unwrap();
+        assert_eq!(sources_hash(&dir).unwrap(), hash);
+
+        fs::write(dir.join("day1").join("mod.rs"), "fn part2() {}").unwrap();
+        let changed = sources_hash(&dir).unwrap();
+        assert_ne!(changed, hash);
+
+        fs::write(dir.join("day2.rs"), "").unwrap();
+        assert_ne!(sources_hash(&dir).unwrap(), changed);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,4 @@
-use std::env::args_os;
+use std::env::{self, args_os};
 use std::ffi::OsString;
 
 /// Clap does not play well with `cargo aoc` syntax (instead of `cargo-aoc` that works out of the box)
@@ -9,3 +9,13 @@ pub fn args_without_aoc() -> impl Iterator<Item = OsString> {
         .filter(|(i, arg)| *i != 1 || arg != "aoc")
         .map(|(_, arg)| arg)
 }
+
+/// Parses the path of an alternate input file, relative to the directory cargo-aoc is run from.
+/// It is made absolute, as cargo-aoc then moves to the directory of the crate.
+pub fn input_path(path: &str) -> Result<String, String> {
+    let absolute = env::current_dir().map_err(|e| e.to_string())?.join(path);
+    if !absolute.is_file() {
+        return Err(format!("Input file not found: {}", path));
+    }
+    Ok(absolute.display().to_string())
+}
@@ -0,0 +1,53 @@
+/// Width of a character in the badges, in pixels, close enough to Verdana 11px
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+/// Renders a flat shields-style badge, with a grey label and a colored message
+pub fn svg(label: &str, message: &str, color: &str) -> String {
+    let label_width = label.chars().count() * CHAR_WIDTH + PADDING;
+    let message_width = message.chars().count() * CHAR_WIDTH + PADDING;
+    let width = label_width + message_width;
+    let label = escape(label);
+    let message = escape(message);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+/// Picks the color of the stars badge, from red to green as the parts get solved
+pub fn stars_color(solved: usize, total: usize) -> &'static str {
+    match solved * 100 / total.max(1) {
+        100.. => "#4c1",
+        75..=99 => "#97ca00",
+        50..=74 => "#dfb317",
+        25..=49 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
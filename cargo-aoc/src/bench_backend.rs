@@ -0,0 +1,155 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::process::{Command, Stdio};
+
+/// The version of iai-callgrind, which must be the one of the `iai-callgrind-runner` installed
+const IAI_CALLGRIND_VERSION: &str = "0.14.0";
+
+/// The dev-dependency profiling the criterion benchmarks, for `--flamegraph`
+pub const PPROF_DEPENDENCY: &str =
+    "pprof = { version = \"0.13.0\", features = [\"flamegraph\", \"criterion\"] }";
+
+/// The library measuring the benchmarks of `cargo aoc bench`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BenchBackend {
+    /// Statistics & HTML reports, but slow to run
+    #[default]
+    Criterion,
+    /// Quick wall-clock numbers, with much less measurement overhead
+    Divan,
+    /// Instruction counts & cache stats measured by callgrind, the same from a run to another
+    IaiCallgrind,
+}
+
+/// The templates of the benchmark harness, filled the same way for every backend
+pub struct Templates {
+    pub bench: &'static str,
+    /// Wraps the parts of a day, in a single criterion group
+    pub day: &'static str,
+    pub part: &'static str,
+    pub gen: &'static str,
+    pub runner: &'static str,
+    pub gen_runner: &'static str,
+}
+
+impl BenchBackend {
+    /// The dev-dependency of the benchmark crate
+    pub fn dependency(self) -> String {
+        match self {
+            BenchBackend::Criterion => "criterion = \"0.5.1\"".into(),
+            BenchBackend::Divan => "divan = \"0.1.14\"".into(),
+            BenchBackend::IaiCallgrind => {
+                format!("iai-callgrind = \"={}\"", IAI_CALLGRIND_VERSION)
+            }
+        }
+    }
+
+    /// Checks that the tools needed to run the benchmarks are installed
+    pub fn check(self) -> Result<(), String> {
+        if self != BenchBackend::IaiCallgrind {
+            return Ok(());
+        }
+
+        if !installed("valgrind") {
+            return Err(
+                "valgrind is needed to benchmark with iai-callgrind, install it first".into(),
+            );
+        }
+        if !installed("iai-callgrind-runner") {
+            return Err(format!(
+                "iai-callgrind-runner is needed to benchmark with iai-callgrind, install it with \
+                 `cargo install iai-callgrind-runner --version {}`",
+                IAI_CALLGRIND_VERSION
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn templates(self) -> Templates {
+        match self {
+            BenchBackend::Criterion => Templates {
+                bench: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/aoc_benchmark.rs.tpl"
+                )),
+                day: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/day.rs.tpl"
+                )),
+                part: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/part.rs.tpl"
+                )),
+                gen: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/gen.rs.tpl"
+                )),
+                runner: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/impl.rs.tpl"
+                )),
+                gen_runner: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/gen_impl.rs.tpl"
+                )),
+            },
+            BenchBackend::Divan => Templates {
+                bench: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/divan/aoc_benchmark.rs.tpl"
+                )),
+                day: "{PARTS}",
+                part: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/divan/part.rs.tpl"
+                )),
+                gen: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/divan/gen.rs.tpl"
+                )),
+                runner: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/divan/impl.rs.tpl"
+                )),
+                gen_runner: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/divan/gen_impl.rs.tpl"
+                )),
+            },
+            BenchBackend::IaiCallgrind => Templates {
+                bench: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/iai/aoc_benchmark.rs.tpl"
+                )),
+                day: "{PARTS}",
+                part: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/iai/part.rs.tpl"
+                )),
+                gen: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/iai/gen.rs.tpl"
+                )),
+                runner: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/iai/impl.rs.tpl"
+                )),
+                gen_runner: include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/template/benches/iai/gen_impl.rs.tpl"
+                )),
+            },
+        }
+    }
+}
+
+/// Tells if a program can be run
+pub fn installed(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
@@ -0,0 +1,110 @@
+use crate::bench_backend;
+use crate::duration;
+use clap::Args;
+use serde::Deserialize;
+use std::env;
+use std::hint;
+use std::time::{Duration, Instant};
+
+/// The conditions the benchmarks run in, to reduce their variance on laptops, where a benchmark
+/// heats the CPU up and slows the next ones down.
+///
+/// They are given on the command line, or in the `[bench.environment]` table of aoc.toml.
+#[derive(Args, Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Environment {
+    /// Pins the benchmarks to this CPU core, with taskset (Linux only).
+    #[clap(long, value_name = "CORE")]
+    pub pin_cpu: Option<usize>,
+
+    /// Keeps the CPU busy for this duration (like 10s) before the first benchmark, for its clock
+    /// speed to settle.
+    #[clap(long, value_parser = duration::parse)]
+    #[serde(deserialize_with = "duration::deserialize_option")]
+    pub warmup: Option<Duration>,
+
+    /// Pauses for this duration (like 5s) between the benchmarks of two parts, for the CPU to
+    /// cool down (criterion only).
+    #[clap(long, value_parser = duration::parse)]
+    #[serde(deserialize_with = "duration::deserialize_option")]
+    pub cooldown: Option<Duration>,
+}
+
+impl Environment {
+    /// Completes the environment with another one, like the one of aoc.toml
+    pub fn or(&self, other: &Environment) -> Environment {
+        Environment {
+            pin_cpu: self.pin_cpu.or(other.pin_cpu),
+            warmup: self.warmup.or(other.warmup),
+            cooldown: self.cooldown.or(other.cooldown),
+        }
+    }
+
+    /// Gets the command line flags giving this environment to another `cargo aoc bench`
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(core) = self.pin_cpu {
+            args.extend(["--pin-cpu".into(), core.to_string()]);
+        }
+        if let Some(warmup) = self.warmup {
+            args.extend(["--warmup".into(), format!("{}ns", warmup.as_nanos())]);
+        }
+        if let Some(cooldown) = self.cooldown {
+            args.extend(["--cooldown".into(), format!("{}ns", cooldown.as_nanos())]);
+        }
+        args
+    }
+
+    /// Gets the flags of `cargo bench` pinning the benchmarks to the chosen core.
+    ///
+    /// Cargo runs them through taskset, set as the runner of every target, so that only the
+    /// benchmarks are pinned, and not the compilation.
+    pub fn cargo_args(&self) -> Result<Vec<String>, String> {
+        let Some(core) = self.pin_cpu else {
+            return Ok(Vec::new());
+        };
+        if !cfg!(target_os = "linux") {
+            return Err("The benchmarks are pinned to a core with taskset, on Linux".into());
+        }
+        if !bench_backend::installed("taskset") {
+            return Err("taskset is needed to pin the benchmarks, it comes with util-linux".into());
+        }
+
+        Ok(vec![
+            "--config".into(),
+            format!(
+                "target.'cfg(all())'.runner = ['taskset', '--cpu-list', '{}']",
+                core
+            ),
+        ])
+    }
+
+    /// Gets the code of the criterion harness pausing between two parts
+    pub fn cooldown_code(&self) -> String {
+        match self.cooldown {
+            Some(cooldown) => format!(
+                "\n\n    std::thread::sleep(std::time::Duration::from_nanos({}));\n",
+                cooldown.as_nanos()
+            ),
+            None => String::new(),
+        }
+    }
+}
+
+/// Keeps a core busy for the given duration
+pub fn warm_up(duration: Duration) {
+    let start = Instant::now();
+    let mut state = 0u64;
+    while start.elapsed() < duration {
+        state = hint::black_box(state.wrapping_mul(6364136223846793005).wrapping_add(1));
+    }
+}
+
+/// Gets the `RUSTFLAGS` building the benchmarks for the CPU of this machine, keeping those of the
+/// environment
+pub fn native_rustflags() -> String {
+    match env::var("RUSTFLAGS") {
+        Ok(flags) if !flags.trim().is_empty() => format!("{} -C target-cpu=native", flags),
+        _ => "-C target-cpu=native".to_string(),
+    }
+}
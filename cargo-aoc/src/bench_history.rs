@@ -0,0 +1,140 @@
+use crate::config::Config;
+use crate::output::BenchRecord;
+use crate::time_format;
+use crate::worktree;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A benchmark of an implementation, kept to find the regressions of the next ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchRun {
+    pub day: u8,
+    pub part: u8,
+    /// The name of the alternative implementation, `null` for the default one
+    pub alt: Option<String>,
+    /// What was measured : the `runner`, or the `generator`
+    pub target: String,
+    /// The commit benchmarked, with a `+` when the working tree had changes, like `1a2b3c4+`
+    pub commit: Option<String>,
+    /// When it was benchmarked, in the RFC 3339 format
+    pub time: String,
+    pub mean_ns: f64,
+    pub std_dev_ns: f64,
+}
+
+impl BenchRun {
+    fn is_same(&self, record: &BenchRecord) -> bool {
+        self.day == record.day
+            && self.part == record.part
+            && self.alt == record.alt
+            && self.target == record.target
+    }
+}
+
+/// An implementation slower than its best run
+#[derive(Debug, Clone)]
+pub struct Regression {
+    pub record: BenchRecord,
+    pub best: BenchRun,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} - Part {}", self.record.day, self.record.part)?;
+        if let Some(alt) = &self.record.alt {
+            write!(f, " - {}", alt)?;
+        }
+        if self.record.target == "generator" {
+            write!(f, " - Generator")?;
+        }
+        write!(
+            f,
+            " : {}, {:.1}% slower than the {} of {}",
+            format_ns(self.record.mean_ns),
+            (self.record.mean_ns / self.best.mean_ns - 1.) * 100.,
+            format_ns(self.best.mean_ns),
+            self.best.commit.as_deref().unwrap_or(&self.best.time)
+        )
+    }
+}
+
+/// Gets the path of the benchmarks of a year : `{account_dir}/{year}/benchmarks.json`
+fn path(year: i32) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/{}/benchmarks.json",
+        Config::get().account_dir(),
+        year
+    ))
+}
+
+/// Loads the benchmarks of a year, the oldest first, none if there is no history yet
+pub fn load(year: i32) -> Result<Vec<BenchRun>, Box<dyn error::Error>> {
+    let path = path(year);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(serde_json::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?)
+}
+
+/// Appends the results of a benchmark to the history of a year
+pub fn append(year: i32, records: &[BenchRecord]) -> Result<(), Box<dyn error::Error>> {
+    let mut runs = load(year)?;
+    let commit = commit();
+    let time = Utc::now().to_rfc3339();
+    runs.extend(records.iter().map(|record| BenchRun {
+        day: record.day,
+        part: record.part,
+        alt: record.alt.clone(),
+        target: record.target.to_string(),
+        commit: commit.clone(),
+        time: time.clone(),
+        mean_ns: record.mean_ns,
+        std_dev_ns: record.std_dev_ns,
+    }));
+
+    let path = path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&runs)?)?;
+    Ok(())
+}
+
+/// Finds the implementations slower than their best run by more than a percentage
+pub fn regressions(runs: &[BenchRun], records: &[BenchRecord], threshold: f64) -> Vec<Regression> {
+    records
+        .iter()
+        .filter_map(|record| {
+            let best = runs
+                .iter()
+                .filter(|run| run.is_same(record))
+                .min_by(|a, b| a.mean_ns.total_cmp(&b.mean_ns))?;
+            (record.mean_ns > best.mean_ns * (1. + threshold / 100.)).then(|| Regression {
+                record: record.clone(),
+                best: best.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Gets the commit of the project, with a `+` when the working tree has changes
+fn commit() -> Option<String> {
+    let mut commit = worktree::git(&["rev-parse", "--short", "HEAD"]).ok()?;
+    commit.truncate(commit.trim_end().len());
+    let status = worktree::git(&["status", "--porcelain"]).ok()?;
+    if !status.trim().is_empty() {
+        commit.push('+');
+    }
+    Some(commit)
+}
+
+fn format_ns(ns: f64) -> String {
+    time_format::format(Duration::from_nanos(ns as u64))
+}
@@ -0,0 +1,385 @@
+//! Reads the session cookie of adventofcode.com from the cookies of a browser.
+//!
+//! The cookie databases of Firefox, Chrome and Chromium are read with the `sqlite3` tool. Chrome
+//! and Chromium encrypt their cookies with a password kept in the keyring of the system, read
+//! with `security` on macOS, and `secret-tool` on Linux. On Windows, they encrypt them with a key
+//! only the browser can read, so only Firefox is supported there.
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, KeyIvInit};
+use directories::BaseDirs;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A browser the session cookie can be read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Browser {
+    Firefox,
+    Chrome,
+    Chromium,
+}
+
+impl Browser {
+    /// Gets the directories holding the profiles of the browser
+    fn roots(self, dirs: &BaseDirs) -> Vec<PathBuf> {
+        let home = dirs.home_dir();
+        match self {
+            Browser::Firefox => vec![
+                home.join(".mozilla/firefox"),
+                home.join("snap/firefox/common/.mozilla/firefox"),
+                home.join("Library/Application Support/Firefox/Profiles"),
+                dirs.data_dir().join("Mozilla/Firefox/Profiles"),
+            ],
+            Browser::Chrome => vec![
+                dirs.config_dir().join("google-chrome"),
+                home.join("Library/Application Support/Google/Chrome"),
+                dirs.data_local_dir().join("Google/Chrome/User Data"),
+            ],
+            Browser::Chromium => vec![
+                dirs.config_dir().join("chromium"),
+                home.join("snap/chromium/common/chromium"),
+                home.join("Library/Application Support/Chromium"),
+                dirs.data_local_dir().join("Chromium/User Data"),
+            ],
+        }
+    }
+
+    /// Gets the cookie database of a profile, if it has one
+    fn cookies(self, profile: &Path) -> Option<PathBuf> {
+        let candidates = match self {
+            Browser::Firefox => vec![profile.join("cookies.sqlite")],
+            // The recent versions moved the database to the Network directory
+            Browser::Chrome | Browser::Chromium => {
+                vec![profile.join("Network/Cookies"), profile.join("Cookies")]
+            }
+        };
+        candidates.into_iter().find(|database| database.exists())
+    }
+
+    /// Gets the name of the application of the browser in the keyring, the one of its password
+    fn keyring_name(self) -> &'static str {
+        match self {
+            Browser::Firefox => "Firefox",
+            Browser::Chrome => "Chrome",
+            Browser::Chromium => "Chromium",
+        }
+    }
+}
+
+/// Finds the session cookie of adventofcode.com, in the most recently used browser profile
+/// having one
+pub fn session_cookie() -> Result<String, String> {
+    check_sqlite3()?;
+
+    let databases = cookie_databases();
+    if databases.is_empty() {
+        let message = "No Firefox, Chrome or Chromium profile found, copy the session cookie from \
+                       the developer tools of the browser instead";
+        return Err(message.into());
+    }
+
+    let mut last_error = None;
+    for (browser, database) in &databases {
+        let cookie = match browser {
+            Browser::Firefox => read_firefox_cookie(database),
+            Browser::Chrome | Browser::Chromium => read_chromium_cookie(*browser, database),
+        };
+        match cookie {
+            Ok(Some(cookie)) => return Ok(cookie),
+            Ok(None) => {}
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| {
+        "No session cookie of adventofcode.com found in Firefox, Chrome or Chromium, log in to the \
+         Advent of Code first"
+            .into()
+    }))
+}
+
+/// Checks that the `sqlite3` tool, reading the cookie databases, is installed
+fn check_sqlite3() -> Result<(), String> {
+    match Command::new("sqlite3").arg("-version").output() {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(format!(
+            "sqlite3 failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(
+            "The cookies of the browsers are read with the sqlite3 tool, which is not installed : \
+             install it (the sqlite3 package of most systems, or \
+             https://sqlite.org/download.html), or copy the session cookie from the developer \
+             tools of the browser instead"
+                .into(),
+        ),
+        Err(e) => Err(format!("Could not run sqlite3 to read the cookies: {}", e)),
+    }
+}
+
+/// Gets the cookie databases of the profiles of every browser, the most recently used first
+fn cookie_databases() -> Vec<(Browser, PathBuf)> {
+    let Some(dirs) = BaseDirs::new() else {
+        return Vec::new();
+    };
+
+    let mut databases: Vec<_> = [Browser::Firefox, Browser::Chrome, Browser::Chromium]
+        .into_iter()
+        .flat_map(|browser| {
+            browser
+                .roots(&dirs)
+                .into_iter()
+                .filter_map(|root| fs::read_dir(root).ok())
+                .flatten()
+                .flatten()
+                .filter_map(move |profile| Some((browser, browser.cookies(&profile.path())?)))
+        })
+        .collect();
+    databases.sort_by_key(|(_, database)| {
+        std::cmp::Reverse(fs::metadata(database).and_then(|m| m.modified()).ok())
+    });
+    databases.dedup();
+    databases
+}
+
+/// Reads the session cookie from a cookie database of Firefox, stored in clear
+fn read_firefox_cookie(database: &Path) -> Result<Option<String>, String> {
+    let cookie = query(
+        database,
+        "SELECT value FROM moz_cookies \
+         WHERE host LIKE '%adventofcode.com' AND name = 'session' \
+         ORDER BY expiry DESC LIMIT 1",
+    )?;
+    Ok((!cookie.is_empty()).then_some(cookie))
+}
+
+/// Reads the session cookie from a cookie database of Chrome or Chromium, decrypting it
+fn read_chromium_cookie(browser: Browser, database: &Path) -> Result<Option<String>, String> {
+    let row = query(
+        database,
+        "SELECT (SELECT value FROM meta WHERE key = 'version'), hex(encrypted_value), value \
+         FROM cookies WHERE host_key LIKE '%adventofcode.com' AND name = 'session' \
+         ORDER BY expires_utc DESC LIMIT 1",
+    )?;
+    if row.is_empty() {
+        return Ok(None);
+    }
+
+    let (version, encrypted, value) = parse_row(&row)
+        .ok_or_else(|| format!("Invalid cookie in {}: {}", database.display(), row))?;
+    if !value.is_empty() {
+        return Ok(Some(value.to_string()));
+    }
+
+    let encrypted = decode_hex(encrypted)
+        .ok_or_else(|| format!("Invalid cookie in {}: {}", database.display(), row))?;
+    let (password, iterations) = password(browser, &encrypted)?;
+    let cookie = decrypt(&encrypted, &password, iterations, version)
+        .ok_or_else(|| format!("Could not decrypt the cookies of {:?}", browser))?;
+    Ok((!cookie.is_empty()).then_some(cookie))
+}
+
+/// Runs a query on a copy of a cookie database, and returns its output.
+///
+/// The browsers lock their database while they run, so a copy of it is read.
+fn query(database: &Path, sql: &str) -> Result<String, String> {
+    let copy = env::temp_dir().join(format!("cargo-aoc-cookies-{}.sqlite", std::process::id()));
+    fs::copy(database, &copy)
+        .map_err(|e| format!("Could not read {}: {}", database.display(), e))?;
+    // The recent changes are in the write-ahead log
+    let wal = wal_path(database);
+    let copy_wal = wal_path(&copy);
+    if wal.exists() {
+        let _ = fs::copy(&wal, &copy_wal);
+    }
+
+    let output = Command::new("sqlite3").arg(&copy).arg(sql).output();
+    let _ = fs::remove_file(&copy);
+    let _ = fs::remove_file(&copy_wal);
+
+    let output = output.map_err(|e| format!("Could not run sqlite3 to read the cookies: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "sqlite3 failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Gets the path of the write-ahead log of a database, next to it
+fn wal_path(database: &Path) -> PathBuf {
+    let mut wal = database.as_os_str().to_owned();
+    wal.push("-wal");
+    PathBuf::from(wal)
+}
+
+/// Splits a row of the cookies of Chrome, as printed by sqlite3 :
+/// `version|encrypted value in hexadecimal|value`
+fn parse_row(row: &str) -> Option<(u32, &str, &str)> {
+    let mut fields = row.splitn(3, '|');
+    let version = fields.next()?.parse().unwrap_or(0);
+    Some((version, fields.next()?, fields.next()?))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Gets the password the cookies of Chrome or Chromium are encrypted with, and the number of
+/// iterations deriving the key from it
+fn password(browser: Browser, encrypted: &[u8]) -> Result<(Vec<u8>, u32), String> {
+    let (program, args) = if cfg!(target_os = "macos") {
+        let service = format!("{} Safe Storage", browser.keyring_name());
+        (
+            "security",
+            vec![
+                "find-generic-password".into(),
+                "-w".into(),
+                "-s".into(),
+                service,
+            ],
+        )
+    } else if cfg!(windows) {
+        return Err(format!(
+            "{:?} encrypts its cookies on Windows with a key only it can read, use Firefox or \
+             copy the session cookie from the developer tools of the browser instead",
+            browser
+        ));
+    } else if encrypted.starts_with(b"v10") {
+        // Without a keyring, the cookies are encrypted with a fixed password
+        return Ok((b"peanuts".to_vec(), 1));
+    } else {
+        let application = browser.keyring_name().to_lowercase();
+        (
+            "secret-tool",
+            vec!["lookup".into(), "application".into(), application],
+        )
+    };
+
+    let output = Command::new(program)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Could not run {} to read the keyring: {}", program, e))?;
+    let password = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || password.is_empty() {
+        return Err(format!(
+            "Could not read the password of the cookies of {:?} from the keyring: {}",
+            browser,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let iterations = if cfg!(target_os = "macos") { 1003 } else { 1 };
+    Ok((password.into_bytes(), iterations))
+}
+
+/// Decrypts a cookie of Chrome or Chromium : prefixed by `v10` or `v11`, and encrypted with
+/// AES-128-CBC, with a key derived from a password.
+///
+/// Since the version 24 of their database, the value starts with the SHA-256 hash of the domain
+/// of the cookie.
+fn decrypt(encrypted: &[u8], password: &[u8], iterations: u32, version: u32) -> Option<String> {
+    let data = encrypted
+        .strip_prefix(b"v10")
+        .or_else(|| encrypted.strip_prefix(b"v11"))?;
+
+    let mut key = [0; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password, b"saltysalt", iterations, &mut key);
+    let iv = [b' '; 16];
+    let value = cbc::Decryptor::<aes::Aes128>::new(&key.into(), &iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(data)
+        .ok()?;
+
+    let value = if version >= 24 {
+        value.get(32..)?
+    } else {
+        &value[..]
+    };
+    String::from_utf8(value.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypt_linux_cookie() {
+        let encrypted =
+            decode_hex("7631308261DA3BEA61B3ACDE7CFF0D927A80971C93AD3C50670D0BDE331C2C65BA6AB2")
+                .unwrap();
+        assert_eq!(
+            decrypt(&encrypted, b"peanuts", 1, 23).as_deref(),
+            Some("53616c7465645f5f")
+        );
+        assert_eq!(decrypt(&encrypted, b"walnuts", 1, 23), None);
+    }
+
+    #[test]
+    fn decrypt_cookie_with_domain_hash() {
+        let encrypted = decode_hex(
+            "763130A30958303FFEAD87BB6264E2DF8238A399314C7104FB242A5066B5E7AE9C71A702AC4D63F6961C\
+             31325406E281B7BC15F1415B991B45E314C4AC94C2C6705AF6",
+        )
+        .unwrap();
+        assert_eq!(
+            decrypt(&encrypted, b"peanuts", 1, 24).as_deref(),
+            Some("53616c7465645f5f")
+        );
+    }
+
+    #[test]
+    fn decrypt_macos_cookie() {
+        let encrypted =
+            decode_hex("76313062221AA8095FBA1B7D9D8F5C450C7F62CDCFB14DA851F36C1CE8BBC762CAED94")
+                .unwrap();
+        assert_eq!(
+            decrypt(&encrypted, b"secret", 1003, 23).as_deref(),
+            Some("53616c7465645f5f")
+        );
+    }
+
+    #[test]
+    fn decrypt_unknown_version() {
+        assert_eq!(decrypt(b"v20abcdefghijklmnop", b"peanuts", 1, 24), None);
+        assert_eq!(decrypt(b"", b"peanuts", 1, 24), None);
+    }
+
+    #[test]
+    fn parse_rows() {
+        assert_eq!(parse_row("24|7631|"), Some((24, "7631", "")));
+        assert_eq!(parse_row("|7631|"), Some((0, "7631", "")));
+        assert_eq!(parse_row("18||53616c74"), Some((18, "", "53616c74")));
+        assert_eq!(parse_row("24|7631"), None);
+    }
+
+    #[test]
+    fn decode_hex_values() {
+        assert_eq!(
+            decode_hex("76313000ff"),
+            Some(vec![0x76, 0x31, 0x30, 0, 0xff])
+        );
+        assert_eq!(decode_hex(""), Some(Vec::new()));
+        assert_eq!(decode_hex("7"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn wal_next_to_the_database() {
+        assert_eq!(
+            wal_path(Path::new("profile/cookies.sqlite")),
+            Path::new("profile/cookies.sqlite-wal")
+        );
+        assert_eq!(
+            wal_path(Path::new("Default/Network/Cookies")),
+            Path::new("Default/Network/Cookies-wal")
+        );
+    }
+}
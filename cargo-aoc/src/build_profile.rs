@@ -0,0 +1,89 @@
+use std::convert::Infallible;
+use std::fs;
+use std::str::FromStr;
+
+/// The cargo profile used to build the generated crates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BuildProfile {
+    #[default]
+    Release,
+    /// The release profile, with debug info for profiling tools
+    Profiling,
+    /// A profile of cargo (`dev`, `release`...) or one defined in the Cargo.toml of the project
+    Named(String),
+}
+
+/// `--profile` alone adds debug info to the release profile, `--profile {name}` selects a profile
+impl FromStr for BuildProfile {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "" => BuildProfile::Profiling,
+            name => BuildProfile::Named(name.to_string()),
+        })
+    }
+}
+
+impl BuildProfile {
+    /// Gets the arguments selecting the profile, for `cargo run` & `cargo bench`
+    pub fn cargo_args(&self, default: &str) -> Vec<String> {
+        match self {
+            BuildProfile::Release | BuildProfile::Profiling => {
+                vec!["--profile".into(), default.into()]
+            }
+            BuildProfile::Named(name) => vec!["--profile".into(), name.clone()],
+        }
+    }
+
+    /// Gets the directory of `target` where cargo puts what is built with the profile
+    pub fn target_dir(&self, default: &str) -> String {
+        let name = match self {
+            BuildProfile::Release | BuildProfile::Profiling => default,
+            BuildProfile::Named(name) => name,
+        };
+        match name {
+            "dev" | "test" => "debug".to_string(),
+            "bench" => "release".to_string(),
+            name => name.to_string(),
+        }
+    }
+
+    /// Gets the `[profile]` tables to add to the manifest of a generated crate.
+    ///
+    /// The profiles of the project are not used by the generated crates, so the selected one is
+    /// copied from the Cargo.toml of the project, with the custom profiles it inherits from.
+    pub fn manifest_section(&self) -> String {
+        let name = match self {
+            BuildProfile::Release => return String::new(),
+            BuildProfile::Profiling => return "[profile.release]\ndebug = true".into(),
+            BuildProfile::Named(name) => name,
+        };
+
+        let profiles = fs::read_to_string("Cargo.toml")
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .and_then(|cargo| cargo.get("profile")?.as_table().cloned())
+            .unwrap_or_default();
+
+        let mut section = toml::Table::new();
+        let mut next = Some(name.as_str());
+        while let Some(name) = next.take() {
+            let Some(profile) = profiles.get(name) else {
+                break;
+            };
+            if section.insert(name.to_string(), profile.clone()).is_some() {
+                // Inheritance loop, cargo will report it
+                break;
+            }
+            next = profile.get("inherits").and_then(|i| i.as_str());
+        }
+
+        if section.is_empty() {
+            return String::new();
+        }
+        let mut root = toml::Table::new();
+        root.insert("profile".into(), section.into());
+        root.to_string()
+    }
+}
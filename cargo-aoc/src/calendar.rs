@@ -0,0 +1,235 @@
+use crate::answers::Answers;
+use crate::color;
+use aoc_runner_internal::{Day, DayParts, Part};
+use chrono::prelude::*;
+use chrono::Duration;
+use chrono_tz::EST;
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+/// The last day of the events until 2024, see [`days`] for the other years
+/// The last day of the event
+pub const LAST_DAY: u32 = 25;
+
+/// Gets the number of puzzles of an event : 25 until 2024, and 12 since 2025
+pub fn days(year: i32) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        LAST_DAY
+    }
+}
+
+/// Gets the number of puzzles of an event unlocked so far
+pub fn unlocked_days(year: i32) -> u32 {
+    (1..=days(year))
+        .filter(|&day| is_unlocked(year, day))
+        .count() as u32
+}
+
+/// Gets the time a puzzle unlocks : midnight in the EST timezone
+pub fn unlock_time(year: i32, day: u32) -> DateTime<Utc> {
+    EST.with_ymd_and_hms(year, 12, day, 0, 0, 0)
+        .single()
+        .expect("December has no DST change")
+        .with_timezone(&Utc)
+}
+
+/// Gets the next puzzle to unlock after a given time, and when it does
+pub fn next_unlock(now: DateTime<Utc>) -> (i32, u32, DateTime<Utc>) {
+    let year = now.with_timezone(&EST).year();
+    (1..=days(year))
+        .map(|day| (year, day, unlock_time(year, day)))
+        .find(|(_, _, unlock)| *unlock > now)
+        .unwrap_or_else(|| (year + 1, 1, unlock_time(year + 1, 1)))
+}
+
+/// Sleeps until a puzzle unlocks, counting down in the terminal
+pub fn wait(year: i32, day: u32) -> io::Result<()> {
+    let message = format!("Day {} of {} unlocks in", day, year);
+    wait_until(unlock_time(year, day), &message)
+}
+
+/// Sleeps until a given time, counting down after a message in the terminal
+pub fn wait_until(until: DateTime<Utc>, message: &str) -> io::Result<()> {
+    let live = io::stdout().is_terminal();
+    let mut shown = false;
+
+    loop {
+        let left = until - Utc::now();
+        if left <= Duration::zero() {
+            if shown && live {
+                println!();
+            }
+            return Ok(());
+        }
+
+        let line = format!("{} {}", message, countdown(left));
+        if live {
+            print!("\r\x1b[K{}", line);
+            io::stdout().flush()?;
+        } else if !shown {
+            println!("{}", line);
+        }
+        shown = true;
+
+        let step = left.to_std().unwrap_or_default();
+        thread::sleep(step.min(std::time::Duration::from_millis(250)));
+    }
+}
+
+/// Tells that a puzzle is not available, with the time left before it unlocks
+pub fn not_unlocked(year: i32, day: u32) -> String {
+    let left = unlock_time(year, day) - Utc::now();
+    if left > Duration::zero() {
+        format!("Day {} unlocks in {}", day, countdown(left))
+    } else {
+        format!("Day {} of {} is not available", day, year)
+    }
+}
+
+/// Tells if a puzzle is unlocked
+pub fn is_unlocked(year: i32, day: u32) -> bool {
+    unlock_time(year, day) <= Utc::now()
+}
+
+/// Formats the time left before an unlock (or anything else), like `2d 03h 12m 05s`
+pub fn countdown(left: Duration) -> String {
+    let seconds = left.num_seconds().max(0);
+    let (days, hours) = (seconds / 86400, seconds / 3600 % 24);
+    let (minutes, seconds) = (seconds / 60 % 60, seconds % 60);
+
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, minutes, seconds)
+    } else {
+        format!("{:02}h {:02}m {:02}s", hours, minutes, seconds)
+    }
+}
+
+/// Renders the calendar of December, a week per line.
+///
+/// Each day is followed by its status : `**` or `*` for the parts having a stored answer,
+/// `o` implemented, `.` unlocked but not implemented, nothing while locked.
+pub fn render(year: i32, day_parts: Option<&DayParts>, answers: &Answers) -> String {
+    let now = Utc::now();
+    let first = NaiveDate::from_ymd_opt(year, 12, 1).expect("invalid year");
+    let offset = first.weekday().num_days_from_monday() as usize;
+
+    let mut out = String::new();
+    let _ = writeln!(out, "December {}\n", year);
+    let week = " Mon   Tue   Wed   Thu   Fri   Sat   Sun";
+    let _ = writeln!(out, "{}", color::paint(color::BOLD, week));
+
+    out += &"      ".repeat(offset);
+    for day in 1..=31u32 {
+        let status = if day > days(year) || unlock_time(year, day) > now {
+            String::new()
+        } else {
+            status(Day(day as u8), day_parts, answers)
+        };
+        // Padded before being colored, the escape codes would count in the width
+        let cell = format!("{:<3}", status);
+        let cell = if status.starts_with('*') {
+            color::paint(color::YELLOW, &cell)
+        } else {
+            cell
+        };
+        let _ = write!(out, " {:>2}{}", day, cell);
+        if (offset + day as usize) % 7 == 0 {
+            out.push('\n');
+        }
+    }
+    out += "\n\n  ** solved   o implemented   . not implemented\n";
+
+    out
+}
+
+/// Gets the status of an unlocked day
+fn status(day: Day, day_parts: Option<&DayParts>, answers: &Answers) -> String {
+    let solved = [Part(1), Part(2)]
+        .into_iter()
+        .filter(|&part| answers.get(day, part).is_some())
+        .count();
+    let implemented = day_parts.is_some_and(|dps| dps.iter().any(|dp| dp.day == day));
+
+    match (solved, implemented) {
+        (0, true) => "o".to_string(),
+        (0, false) => ".".to_string(),
+        (solved, _) => "*".repeat(solved),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn days_of_the_events() {
+        assert_eq!(days(2015), 25);
+        assert_eq!(days(2024), 25);
+        assert_eq!(days(2025), 12);
+        assert_eq!(days(2026), 12);
+    }
+
+    #[test]
+    fn unlock_at_midnight_est() {
+        assert_eq!(unlock_time(2024, 1), utc(2024, 12, 1, 5));
+        assert_eq!(unlock_time(2025, 12), utc(2025, 12, 12, 5));
+    }
+
+    #[test]
+    fn next_unlock_during_an_event() {
+        assert_eq!(
+            next_unlock(utc(2024, 12, 20, 6)),
+            (2024, 21, utc(2024, 12, 21, 5))
+        );
+        assert_eq!(
+            next_unlock(utc(2024, 12, 24, 12)),
+            (2024, 25, utc(2024, 12, 25, 5))
+        );
+        assert_eq!(
+            next_unlock(utc(2025, 12, 11, 4)),
+            (2025, 11, utc(2025, 12, 11, 5))
+        );
+        assert_eq!(
+            next_unlock(utc(2025, 12, 11, 6)),
+            (2025, 12, utc(2025, 12, 12, 5))
+        );
+    }
+
+    #[test]
+    fn next_unlock_after_an_event() {
+        // The last puzzle of 2025 unlocked on the 12th, there is no day 13
+        assert_eq!(
+            next_unlock(utc(2025, 12, 13, 6)),
+            (2026, 1, utc(2026, 12, 1, 5))
+        );
+        assert_eq!(
+            next_unlock(utc(2024, 12, 26, 6)),
+            (2025, 1, utc(2025, 12, 1, 5))
+        );
+        // Still the 31st of December in the EST timezone
+        assert_eq!(
+            next_unlock(utc(2026, 1, 1, 2)),
+            (2026, 1, utc(2026, 12, 1, 5))
+        );
+        assert_eq!(
+            next_unlock(utc(2026, 7, 14, 12)),
+            (2026, 1, utc(2026, 12, 1, 5))
+        );
+    }
+
+    #[test]
+    fn countdowns() {
+        assert_eq!(countdown(Duration::seconds(3725)), "01h 02m 05s");
+        assert_eq!(
+            countdown(Duration::seconds(2 * 86400 + 59)),
+            "2d 00h 00m 59s"
+        );
+        assert_eq!(countdown(Duration::seconds(-5)), "00h 00m 00s");
+    }
+}
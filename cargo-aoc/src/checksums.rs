@@ -0,0 +1,92 @@
+use crate::config::Config;
+use crate::date::AOCDate;
+use std::collections::BTreeMap;
+use std::error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The state of a downloaded input, compared to its checksum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputState {
+    /// The input is the one downloaded
+    Ok,
+    /// The input was changed since it was downloaded
+    Modified,
+    /// The input was downloaded before the checksums were stored
+    Unknown,
+    Missing,
+}
+
+/// The checksums of the inputs of a year, stored in `{account_dir}/{year}/checksums.json`
+#[derive(Debug, Default)]
+pub struct Checksums {
+    checksums: BTreeMap<u32, String>,
+}
+
+impl Checksums {
+    fn path(year: i32) -> PathBuf {
+        PathBuf::from(format!(
+            "{}/{}/checksums.json",
+            Config::get().account_dir(),
+            year
+        ))
+    }
+
+    /// Loads the checksums of a year, an empty store is returned if there is no checksums file
+    pub fn load(year: i32) -> Result<Self, Box<dyn error::Error>> {
+        let path = Checksums::path(year);
+
+        let checksums = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Checksums { checksums })
+    }
+
+    /// Writes the checksums of a year to its checksums file
+    pub fn save(&self, year: i32) -> Result<(), Box<dyn error::Error>> {
+        let path = Checksums::path(year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&self.checksums)?)?;
+        Ok(())
+    }
+
+    /// Compares the input of a day to its checksum
+    pub fn state(&self, date: AOCDate) -> InputState {
+        let Ok(input) = fs::read(date.filename()) else {
+            return InputState::Missing;
+        };
+        match self.checksums.get(&date.day) {
+            Some(expected) if *expected == checksum(&input) => InputState::Ok,
+            Some(_) => InputState::Modified,
+            None => InputState::Unknown,
+        }
+    }
+}
+
+/// Stores the checksum of a downloaded input.
+///
+/// The inputs of `input --all` are downloaded at once, so the file is only updated by one of them
+/// at a time.
+pub fn record(date: AOCDate, input: &[u8]) -> Result<(), Box<dyn error::Error>> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    let _lock = LOCK.lock().unwrap();
+
+    let mut checksums = Checksums::load(date.year)?;
+    checksums.checksums.insert(date.day, checksum(input));
+    checksums.save(date.year)
+}
+
+/// Gets the FNV-1a hash of an input, which stays the same from a version of Rust to another
+fn checksum(input: &[u8]) -> String {
+    let hash = input.iter().fold(0xcbf29ce484222325u64, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,44 @@
+use std::error;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// The commands writing their input to the clipboard, tried in order
+#[cfg(target_os = "macos")]
+const COMMANDS: &[&[&str]] = &[&["pbcopy"]];
+#[cfg(windows)]
+const COMMANDS: &[&[&str]] = &[&["clip"]];
+#[cfg(not(any(target_os = "macos", windows)))]
+const COMMANDS: &[&[&str]] = &[
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+/// Copies some text to the clipboard, with the first clipboard tool found on the system
+pub fn copy(text: &str) -> Result<(), Box<dyn error::Error>> {
+    for command in COMMANDS {
+        let Ok(mut child) = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    let tools: Vec<_> = COMMANDS.iter().map(|command| command[0]).collect();
+    Err(format!(
+        "Could not copy to the clipboard, install one of: {}",
+        tools.join(", ")
+    )
+    .into())
+}
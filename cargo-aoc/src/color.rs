@@ -0,0 +1,72 @@
+use crate::time_format;
+use clap::ValueEnum;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// When to color the output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// When printing to a terminal, and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+pub const BOLD: &str = "1";
+pub const RED: &str = "31";
+pub const GREEN: &str = "32";
+pub const YELLOW: &str = "33";
+
+/// Timings under this are shown in green
+pub const FAST: Duration = Duration::from_millis(10);
+/// Timings under this are shown in yellow, and in red above
+pub const SLOW: Duration = Duration::from_secs(1);
+
+/// Sets whether the output is colored, must be called before printing anything
+pub fn init(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+        }
+    };
+    let _ = ENABLED.set(enabled);
+}
+
+pub fn enabled() -> bool {
+    *ENABLED.get().unwrap_or(&false)
+}
+
+/// Wraps a text with an ANSI style, when colors are enabled
+pub fn paint(style: &str, text: &str) -> String {
+    if enabled() {
+        format!("\x1b[{}m{}\x1b[0m", style, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Gets the style of a timing : green when fast, yellow, or red when slow
+pub fn time_style(duration: Duration) -> &'static str {
+    if duration < FAST {
+        GREEN
+    } else if duration < SLOW {
+        YELLOW
+    } else {
+        RED
+    }
+}
+
+/// Formats & colors a timing, padded to the given width
+pub fn time(duration: Duration, width: usize) -> String {
+    paint(
+        time_style(duration),
+        &format!("{:>width$}", time_format::format(duration)),
+    )
+}
@@ -0,0 +1,52 @@
+//! Compresses the cached inputs and puzzles with `zstd`, with `compress = true` in aoc.toml.
+//!
+//! The inputs are then stored like `input/2023/day1.txt.zst`, and decompressed in
+//! `target/aoc/inputs` to run the solutions, the same way as the encrypted ones.
+
+use crate::config::Config;
+use std::error;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// The extension of the compressed files
+pub const EXTENSION: &str = "zst";
+
+/// Tells if the inputs and puzzles are stored compressed
+pub fn enabled() -> bool {
+    Config::get().compress
+}
+
+pub fn compress(data: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    zstd(&["--quiet", "--stdout", "-19"], data)
+}
+
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    zstd(&["--quiet", "--stdout", "--decompress"], data)
+}
+
+/// Runs zstd on some data, and returns its standard output
+fn zstd(args: &[&str], data: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    let mut child = Command::new("zstd")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Could not run zstd to compress the inputs: {}", e))?;
+
+    // Written from another thread, zstd could wait for its output to be read otherwise
+    let mut stdin = child.stdin.take().ok_or("Could not write to zstd")?;
+    let data = data.to_vec();
+    let writer = std::thread::spawn(move || stdin.write_all(&data));
+
+    let output = child.wait_with_output()?;
+    writer.join().map_err(|_| "Could not write to zstd")??;
+    if !output.status.success() {
+        return Err(format!(
+            "zstd failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(output.stdout)
+}
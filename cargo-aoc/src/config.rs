@@ -0,0 +1,194 @@
+use crate::bench_backend::BenchBackend;
+use crate::bench_env;
+use crate::build_profile::BuildProfile;
+use crate::criterion;
+use crate::duration;
+use crate::stack_size;
+use aoc_runner_internal::Day;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The project configuration, read from `aoc.toml` at the root of the crate.
+///
+/// Every setting is optional, and the command line flags take precedence over it.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The year used when it can't be read from the project
+    pub year: Option<i32>,
+    /// Adds debug info for profiling tools
+    pub profile: bool,
+    /// The directory where inputs, answers & examples are stored
+    pub input_dir: Option<String>,
+    /// The path of the inputs, like `"inputs/{year}/day{day:02}.txt"`
+    pub input_path: Option<String>,
+    /// The credentials profile holding the session token
+    pub session_profile: Option<String>,
+    /// Checks once a day for a new version of cargo-aoc, and tells when a new event started
+    pub check_updates: bool,
+    /// Sends a desktop notification when a run takes longer than this, like `"30s"`
+    #[serde(deserialize_with = "duration::deserialize_option")]
+    pub notify_after: Option<Duration>,
+    /// The stack size of the threads running the solutions, like `"256MB"`
+    #[serde(deserialize_with = "stack_size::deserialize_option")]
+    pub stack_size: Option<usize>,
+    /// How many times an input or a puzzle is requested before giving up, on network errors
+    pub download_attempts: u32,
+    /// The proxy the requests are sent through, like `"http://proxy:8080"`
+    pub proxy: Option<String>,
+    /// Forbids every network access, only the cached inputs being used
+    pub offline: bool,
+    /// The address of the Advent of Code, to use a mirror or a test server
+    pub base_url: Option<String>,
+    /// Stores the inputs encrypted, like `[encryption]`
+    pub encryption: Option<EncryptionConfig>,
+    /// Stores the inputs and the puzzles compressed with zstd
+    pub compress: bool,
+    /// Creates a `.gitignore` in the input directory, so that the inputs aren't committed
+    pub ignore_inputs: bool,
+    /// Also reads and writes the inputs in the cache of another tool, like `"aocd"`
+    pub shared_cache: Option<SharedCache>,
+    pub bench: BenchConfig,
+    /// Per day settings, like `[days.7]`
+    pub days: BTreeMap<String, DayConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            profile: false,
+            input_dir: None,
+            input_path: None,
+            session_profile: None,
+            check_updates: true,
+            notify_after: None,
+            stack_size: None,
+            download_attempts: 4,
+            proxy: None,
+            offline: false,
+            base_url: None,
+            encryption: None,
+            compress: false,
+            ignore_inputs: true,
+            shared_cache: None,
+            bench: BenchConfig::default(),
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchConfig {
+    /// Opens the benchmark information in the browser
+    pub open: bool,
+    /// Also benchmark generator functions
+    pub generator: bool,
+    /// Builds the benchmarks for the CPU of this machine
+    pub native_cpu: bool,
+    /// The library measuring the benchmarks
+    pub backend: Option<BenchBackend>,
+    /// The settings of criterion, like `[bench.criterion]`
+    pub criterion: criterion::Settings,
+    /// The conditions the benchmarks run in, like `[bench.environment]`
+    pub environment: bench_env::Environment,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionConfig {
+    pub tool: EncryptionTool,
+    /// The age recipient, or the gpg key, the inputs are encrypted for
+    pub recipient: String,
+    /// The file holding the age key decrypting the inputs
+    pub identity: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncryptionTool {
+    Age,
+    Gpg,
+}
+
+/// The tools whose cache of inputs can be shared
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SharedCache {
+    /// The `advent-of-code-data` Python package
+    Aocd,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayConfig {
+    /// An alternate input file for this day
+    pub input: Option<String>,
+    pub profile: Option<bool>,
+}
+
+impl Config {
+    /// Gets the configuration of the project, loading it on the first call.
+    ///
+    /// Each crate of a workspace has its own, `--all-years` moving from a crate to another.
+    pub fn get() -> &'static Config {
+        static CONFIGS: Mutex<BTreeMap<PathBuf, &'static Config>> = Mutex::new(BTreeMap::new());
+
+        let dir = env::current_dir().unwrap_or_default();
+        let mut configs = CONFIGS.lock().unwrap();
+        *configs.entry(dir).or_insert_with(|| {
+            let config = match fs::read_to_string("aoc.toml") {
+                Ok(content) => toml::from_str(&content).expect("Failed to parse aoc.toml"),
+                Err(_) => Config::default(),
+            };
+            Box::leak(Box::new(config))
+        })
+    }
+
+    pub fn input_dir(&self) -> &str {
+        self.input_dir.as_deref().unwrap_or("input")
+    }
+
+    /// Gets the directory of the files depending on the account, like the inputs and the answers :
+    /// `{input_dir}/{session_profile}` when a session profile is set, since the inputs differ
+    /// from an account to another
+    pub fn account_dir(&self) -> String {
+        match &self.session_profile {
+            Some(profile) => format!("{}/{}", self.input_dir(), profile),
+            None => self.input_dir().to_string(),
+        }
+    }
+
+    pub fn day(&self, day: Day) -> Option<&DayConfig> {
+        self.days.get(&day.0.to_string())
+    }
+
+    /// Gets the alternate input of a day, the one given on the command line first
+    pub fn input<'a>(&'a self, day: Day, input: Option<&'a str>) -> Option<&'a str> {
+        input.or_else(|| self.day(day).and_then(|d| d.input.as_deref()))
+    }
+
+    /// Gets the build profile of a day, the one given on the command line first.
+    /// Otherwise, the settings tell if debug info should be added to the release profile.
+    pub fn profile(&self, day: Option<Day>, profile: Option<&BuildProfile>) -> BuildProfile {
+        if let Some(profile) = profile {
+            return profile.clone();
+        }
+
+        let debug_info = day
+            .and_then(|d| self.day(d))
+            .and_then(|d| d.profile)
+            .unwrap_or(self.profile);
+        if debug_info {
+            BuildProfile::Profiling
+        } else {
+            BuildProfile::Release
+        }
+    }
+}
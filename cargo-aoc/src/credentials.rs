@@ -1,9 +1,20 @@
+use crate::config::Config;
+use crate::http;
+use crate::keyring;
+use chrono::{DateTime, Local};
 use directories::ProjectDirs;
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::env;
 use std::fs;
 use std::path::PathBuf;
 
+/// The environment variable holding a session token, used instead of the stored one
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
 pub struct CredentialsManager {
     session_token: Option<String>,
+    source: Option<&'static str>,
 }
 
 impl CredentialsManager {
@@ -26,6 +37,15 @@ impl CredentialsManager {
 
     /// Gets an instance of the CredentialManager
     pub fn new() -> Self {
+        // The environment variable takes precedence, without even reading the file, for CI
+        // and containers which may have no config directory
+        if let Some(token) = env::var(SESSION_ENV).ok().filter(|t| !t.trim().is_empty()) {
+            return CredentialsManager {
+                session_token: Some(token.trim().to_string()),
+                source: Some("the AOC_SESSION environment variable"),
+            };
+        }
+
         // Gets a reference to the local credentials.toml file
         let path_buf = CredentialsManager::get_credentials_file();
 
@@ -42,14 +62,33 @@ impl CredentialsManager {
         }
 
         // Reads it
+        let mut source = None;
         let token: Option<String> = match fs::read_to_string(path_buf) {
             // If we can read the TOML file
             Ok(content) => {
                 // Parse the credentials.toml file
                 let creds: toml::Value = content.parse().expect("Failed to parse credentials.toml");
-                // Returns the parsed credentials' session value.
-                // (or None, if we did not get anything.)
-                creds["session"].as_str().map(|s| s.into())
+                let table = CredentialsManager::session_table(&creds);
+                // With `keyring = true`, the token is in the keyring of the system,
+                // the session value of the file being a fallback
+                let in_keyring = table
+                    .and_then(|t| t.get("keyring"))
+                    .and_then(|k| k.as_bool())
+                    .unwrap_or(false);
+                let from_keyring = in_keyring
+                    .then(|| keyring::get(&CredentialsManager::keyring_account()).ok())
+                    .flatten();
+                source = from_keyring.as_ref().map(|_| "the keyring of the system");
+                from_keyring.or_else(|| {
+                    // Returns the parsed credentials' session value.
+                    // (or None, if we did not get anything.)
+                    let token = table
+                        .and_then(|t| t.get("session"))
+                        .and_then(|s| s.as_str())
+                        .map(|s| s.into());
+                    source = token.as_ref().map(|_| "credentials.toml");
+                    token
+                })
             }
             // If we cant, just say that we did not get the token
             Err(_) => None,
@@ -57,9 +96,27 @@ impl CredentialsManager {
 
         CredentialsManager {
             session_token: token,
+            source,
         }
     }
 
+    /// Gets the table holding the session token : the root of the file, or the
+    /// `[profiles.{name}]` table when a session profile is set in aoc.toml
+    fn session_table(creds: &toml::Value) -> Option<&toml::Value> {
+        match &Config::get().session_profile {
+            Some(profile) => creds.get("profiles").and_then(|p| p.get(profile)),
+            None => Some(creds),
+        }
+    }
+
+    /// Gets the account of the session token in the keyring : the session profile, or `default`
+    pub fn keyring_account() -> String {
+        Config::get()
+            .session_profile
+            .clone()
+            .unwrap_or_else(|| "default".into())
+    }
+
     /// Attempt to get the session token of the user if it is referenced
     /// in the credentials.toml file. Returns an error otherwise.
     pub fn get_session_token(&self) -> Result<String, String> {
@@ -68,16 +125,145 @@ impl CredentialsManager {
             .ok_or_else(|| "No session token available".into())
     }
 
-    /// Attempt to set the session token of the user in credentials.toml
+    /// Tells where the session token was found, if there is one
+    pub fn token_source(&self) -> Option<&'static str> {
+        self.source
+    }
+
+    /// Attempt to set the session token of the user in credentials.toml, or in the keyring of the
+    /// system, credentials.toml then only telling to look there.
     /// Returns an error in case of an IO error or something ...
-    pub fn set_session_token(&mut self, token: String) -> Result<(), std::io::Error> {
+    pub fn set_session_token(
+        &mut self,
+        token: String,
+        use_keyring: bool,
+    ) -> Result<(), std::io::Error> {
         // Gets a reference to the local credentials.toml file
-        let toml_str = format!("session = \"{}\"", token);
+        let path_buf = CredentialsManager::get_credentials_file();
+
+        // Keeps the tokens of the other profiles
+        let mut creds: toml::Table = fs::read_to_string(&path_buf)
+            .ok()
+            .and_then(|content| content.parse().ok())
+            .unwrap_or_default();
+        let table = match &Config::get().session_profile {
+            Some(profile) => creds
+                .entry("profiles")
+                .or_insert_with(|| toml::Table::new().into())
+                .as_table_mut()
+                .and_then(|profiles| {
+                    profiles
+                        .entry(profile.as_str())
+                        .or_insert_with(|| toml::Table::new().into())
+                        .as_table_mut()
+                }),
+            None => Some(&mut creds),
+        }
+        .ok_or_else(|| std::io::Error::other("Invalid profiles in credentials.toml"))?;
+        // Falls back to the file when the keyring can't be used
+        let in_keyring = use_keyring
+            && match keyring::set(&CredentialsManager::keyring_account(), &token) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!(
+                        "Couldn't use the keyring ({}), storing the token in the file",
+                        e
+                    );
+                    false
+                }
+            };
+        if in_keyring {
+            table.remove("session");
+            table.insert("keyring".into(), true.into());
+        } else {
+            table.remove("keyring");
+            table.insert("session".into(), token.clone().into());
+        }
+
         // Sets the information of this struct
         self.session_token = Some(token);
-        let path_buf = CredentialsManager::get_credentials_file();
+        self.source = Some(if in_keyring {
+            "the keyring of the system"
+        } else {
+            "credentials.toml"
+        });
 
         // Writes the session token to the file
-        fs::write(path_buf, toml_str)
+        fs::write(path_buf, creds.to_string())
+    }
+}
+
+/// Tells if adventofcode.com answered as it does to an expired or malformed session token :
+/// with its `Please log in` message, or a 400 or 500 status.
+///
+/// Only the answers of the inputs and of the submissions are checked, their 500 status not being
+/// retried by [`http::get`] : the other errors of the server are.
+pub fn session_expired(status: StatusCode, body: &str) -> bool {
+    status == StatusCode::BAD_REQUEST
+        || status == StatusCode::INTERNAL_SERVER_ERROR
+        || body.contains("Please log in")
+}
+
+/// The error given when the session token seems expired
+pub fn session_expired_error() -> String {
+    format!(
+        "Your session token appears expired (or malformed), copy it again from your browser \
+         and run `cargo aoc credentials {{token}}`, or update {}",
+        SESSION_ENV
+    )
+}
+
+/// The file holding when the session token of each profile was last verified
+fn verified_file() -> Option<PathBuf> {
+    http::cache_dir().map(|dir| dir.join("verified-tokens.json"))
+}
+
+/// Gets when the session token of the current profile was last found valid
+pub fn last_verified() -> Option<DateTime<Local>> {
+    let content = fs::read_to_string(verified_file()?).ok()?;
+    let verified: BTreeMap<String, String> = serde_json::from_str(&content).ok()?;
+    let time = verified.get(&CredentialsManager::keyring_account())?;
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|time| time.with_timezone(&Local))
+}
+
+/// Stores that the session token of the current profile was just found valid
+pub fn set_verified() -> std::io::Result<()> {
+    let Some(path) = verified_file() else {
+        return Ok(());
+    };
+    let mut verified: BTreeMap<String, String> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    verified.insert(
+        CredentialsManager::keyring_account(),
+        Local::now().to_rfc3339(),
+    );
+    fs::write(path, serde_json::to_string_pretty(&verified)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_session() {
+        assert!(session_expired(StatusCode::BAD_REQUEST, ""));
+        assert!(session_expired(StatusCode::INTERNAL_SERVER_ERROR, ""));
+        assert!(session_expired(
+            StatusCode::OK,
+            "<p>To play, please identify yourself via one of these services:</p>\
+             <p>Please log in to get your puzzle input.</p>"
+        ));
+    }
+
+    #[test]
+    fn valid_session() {
+        assert!(!session_expired(StatusCode::OK, "12345\n67890\n"));
+        assert!(!session_expired(StatusCode::NOT_FOUND, ""));
+        assert!(!session_expired(StatusCode::BAD_GATEWAY, ""));
+        assert!(!session_expired(StatusCode::SERVICE_UNAVAILABLE, ""));
     }
 }
@@ -0,0 +1,208 @@
+use crate::duration;
+use aoc_runner_internal::Part;
+use clap::Args;
+use serde::Deserialize;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The settings of criterion, used by the generated harness instead of its defaults.
+///
+/// They are given on the command line, or in the `[bench.criterion]` table of aoc.toml.
+#[derive(Args, Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// The number of samples of each benchmark, at least 10 (100 by default).
+    #[clap(long)]
+    pub sample_size: Option<usize>,
+
+    /// How long each benchmark is measured, like 30s (5s by default).
+    #[clap(long, value_parser = duration::parse)]
+    #[serde(deserialize_with = "duration::deserialize_option")]
+    pub measurement_time: Option<Duration>,
+
+    /// How long each benchmark runs before being measured, like 1s (3s by default).
+    #[clap(long, value_parser = duration::parse)]
+    #[serde(deserialize_with = "duration::deserialize_option")]
+    pub warm_up_time: Option<Duration>,
+
+    /// The changes ignored when comparing to the last run, like 0.05 for 5% (0.01 by default).
+    #[clap(long)]
+    pub noise_threshold: Option<f64>,
+
+    /// The significance level of the comparisons to the last run (0.05 by default).
+    #[clap(long)]
+    pub significance_level: Option<f64>,
+}
+
+impl Settings {
+    /// Completes the settings with other ones, like those of aoc.toml
+    pub fn or(&self, other: &Settings) -> Settings {
+        Settings {
+            sample_size: self.sample_size.or(other.sample_size),
+            measurement_time: self.measurement_time.or(other.measurement_time),
+            warm_up_time: self.warm_up_time.or(other.warm_up_time),
+            noise_threshold: self.noise_threshold.or(other.noise_threshold),
+            significance_level: self.significance_level.or(other.significance_level),
+        }
+    }
+
+    /// Gets the command line flags giving those settings to another `cargo aoc bench`
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(sample_size) = self.sample_size {
+            args.extend(["--sample-size".into(), sample_size.to_string()]);
+        }
+        if let Some(time) = self.measurement_time {
+            args.extend([
+                "--measurement-time".into(),
+                format!("{}ns", time.as_nanos()),
+            ]);
+        }
+        if let Some(time) = self.warm_up_time {
+            args.extend(["--warm-up-time".into(), format!("{}ns", time.as_nanos())]);
+        }
+        if let Some(threshold) = self.noise_threshold {
+            args.extend(["--noise-threshold".into(), threshold.to_string()]);
+        }
+        if let Some(level) = self.significance_level {
+            args.extend(["--significance-level".into(), level.to_string()]);
+        }
+        args
+    }
+
+    /// Gets the expression building the `Criterion` of the harness. The settings are checked
+    /// first, since criterion panics on the invalid ones
+    pub fn expression(&self, flamegraph: bool) -> Result<String, String> {
+        let mut expression = "Criterion::default()".to_string();
+        if let Some(sample_size) = self.sample_size {
+            if sample_size < 10 {
+                return Err("The sample size must be at least 10".into());
+            }
+            expression += &format!(".sample_size({})", sample_size);
+        }
+        if let Some(time) = self.measurement_time {
+            if time.is_zero() {
+                return Err("The measurement time can't be zero".into());
+            }
+            expression += &format!(
+                ".measurement_time(std::time::Duration::from_nanos({}))",
+                time.as_nanos()
+            );
+        }
+        if let Some(time) = self.warm_up_time {
+            if time.is_zero() {
+                return Err("The warm up time can't be zero".into());
+            }
+            expression += &format!(
+                ".warm_up_time(std::time::Duration::from_nanos({}))",
+                time.as_nanos()
+            );
+        }
+        if let Some(threshold) = self.noise_threshold {
+            if threshold < 0. || threshold.is_nan() {
+                return Err("The noise threshold can't be negative".into());
+            }
+            expression += &format!(".noise_threshold({:?})", threshold);
+        }
+        if let Some(level) = self.significance_level {
+            if level <= 0. || level >= 1. || level.is_nan() {
+                return Err("The significance level must be between 0 and 1".into());
+            }
+            expression += &format!(".significance_level({:?})", level);
+        }
+        if flamegraph {
+            expression += ".with_profiler(pprof::criterion::PProfProfiler::new(\
+                           100, pprof::criterion::Output::Flamegraph(None)))";
+        }
+        Ok(expression)
+    }
+}
+
+/// The results of a benchmark, read from the `estimates.json` file written by criterion
+#[derive(Debug, Clone, Deserialize)]
+pub struct Estimates {
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub std_dev: Estimate,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Estimate {
+    /// In nanoseconds
+    pub point_estimate: f64,
+    pub confidence_interval: ConfidenceInterval,
+}
+
+/// The bounds the estimate is in, with a given confidence
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfidenceInterval {
+    /// Between 0 and 1, 0.95 by default
+    pub confidence_level: f64,
+    /// In nanoseconds
+    pub lower_bound: f64,
+    /// In nanoseconds
+    pub upper_bound: f64,
+}
+
+impl Estimates {
+    /// Loads the estimates of the last run of a benchmark
+    pub fn load(
+        criterion_dir: &Path,
+        group: &str,
+        bench: &str,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        let path = bench_dir(criterion_dir, group, bench).join("new/estimates.json");
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Loads the estimates of a benchmark from the first of the groups having it
+    pub fn load_first(
+        criterion_dir: &Path,
+        groups: &[String],
+        bench: &str,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        let mut error = None;
+        for group in groups {
+            match Self::load(criterion_dir, group, bench) {
+                Ok(estimates) => return Ok(estimates),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        Err(error.unwrap_or_else(|| "No benchmark group".into()))
+    }
+}
+
+/// Gets the id of the benchmark of an implementation, in the group of its day
+pub fn bench_id(part: Part, name: Option<&str>) -> String {
+    format!("part{}/{}", part.0, name.unwrap_or("(default)"))
+}
+
+/// Gets the flamegraph written by pprof when profiling a benchmark, with `--profile-time`
+pub fn flamegraph(criterion_dir: &Path, group: &str, bench: &str) -> PathBuf {
+    bench_dir(criterion_dir, group, bench).join("profile/flamegraph.svg")
+}
+
+/// Gets the directory of the results of a benchmark
+fn bench_dir(criterion_dir: &Path, group: &str, bench: &str) -> PathBuf {
+    criterion_dir
+        .join(directory_name(group))
+        .join(directory_name(bench))
+}
+
+/// Mimics the way criterion turns benchmark ids into directory names
+fn directory_name(id: &str) -> String {
+    id.chars()
+        .map(|c| match c {
+            '?' | '"' | '/' | '\\' | '*' | '<' | '>' | ':' | '|' | '^' => '_',
+            c => c,
+        })
+        .collect()
+}
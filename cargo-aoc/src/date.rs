@@ -1,8 +1,15 @@
 use chrono::prelude::*;
 use chrono_tz::EST;
 
+use crate::compression;
+use crate::config::Config;
+use crate::encryption;
+use crate::http;
 use crate::Input;
 
+/// The path of the inputs, when there's no `input_path` in aoc.toml
+const DEFAULT_INPUT_PATH: &str = "{input_dir}/{year}/day{day}.txt";
+
 #[derive(Debug, Clone, Copy)]
 pub struct AOCDate {
     /// The day of the input to retrieve
@@ -13,33 +20,110 @@ pub struct AOCDate {
 
 impl AOCDate {
     pub fn new(matches: &Input) -> Self {
-        // Get the current date in the EST timezone, which is used by advent of code to
-        // release new puzzles.
+        let today = AOCDate::today();
+        let day: u32 = matches.day.map(|d| d.0 as u32).unwrap_or(today.day);
+
+        let year: i32 = matches.year.or(Config::get().year).unwrap_or(today.year);
+
+        AOCDate { day, year }
+    }
+
+    /// Gets the current date in the EST timezone, which is used by advent of code to
+    /// release new puzzles.
+    pub fn today() -> Self {
         let utc_today = Utc::now().naive_utc();
         let today = EST.from_utc_datetime(&utc_today);
-        let day: u32 = matches
-            .day
-            .map(|d| d.0 as u32)
-            .unwrap_or_else(|| today.day());
 
-        let year: i32 = matches.year.unwrap_or_else(|| today.year());
+        AOCDate {
+            day: today.day(),
+            year: today.year(),
+        }
+    }
 
-        AOCDate { day, year }
+    /// Gets the puzzle released today, if Advent of Code is running
+    pub fn current_puzzle() -> Option<Self> {
+        let utc_today = Utc::now().naive_utc();
+        let today = EST.from_utc_datetime(&utc_today);
+
+        if today.month() == 12 && today.day() <= 25 {
+            Some(AOCDate {
+                day: today.day(),
+                year: today.year(),
+            })
+        } else {
+            None
+        }
     }
 
     pub fn directory(&self) -> String {
-        format!("input/{}", self.year)
+        format!("{}/{}", Config::get().account_dir(), self.year)
     }
 
+    /// Gets the path of the input, decrypted in `target/aoc/inputs` when the inputs are compressed
+    /// or encrypted
     pub fn filename(&self) -> String {
-        format!("input/{}/day{}.txt", self.year, self.day)
+        if self.packed_filename().is_some() {
+            return match &Config::get().session_profile {
+                Some(profile) => format!(
+                    "target/aoc/inputs/{}/{}/day{}.txt",
+                    profile, self.year, self.day
+                ),
+                None => format!("target/aoc/inputs/{}/day{}.txt", self.year, self.day),
+            };
+        }
+        self.stored_filename()
+    }
+
+    /// Gets the path of the input when it's compressed or encrypted, like
+    /// `{input_dir}/{year}/day{day}.txt.zst.age`
+    pub fn packed_filename(&self) -> Option<String> {
+        let encryption = encryption::config();
+        if !compression::enabled() && encryption.is_none() {
+            return None;
+        }
+
+        let mut path = self.stored_filename();
+        if compression::enabled() {
+            path = format!("{}.{}", path, compression::EXTENSION);
+        }
+        if let Some(config) = encryption {
+            path = format!("{}.{}", path, config.tool.extension());
+        }
+        Some(path)
+    }
+
+    /// Gets the path the input is stored at, from the `input_path` template of aoc.toml
+    /// (`{input_dir}/{year}/day{day}.txt` by default).
+    ///
+    /// The input directory holds a directory per session profile, if one is set.
+    fn stored_filename(&self) -> String {
+        let config = Config::get();
+        config
+            .input_path
+            .as_deref()
+            .unwrap_or(DEFAULT_INPUT_PATH)
+            .replace("{input_dir}", &config.account_dir())
+            .replace(
+                "{profile}",
+                config.session_profile.as_deref().unwrap_or("default"),
+            )
+            .replace("{year}", &self.year.to_string())
+            .replace("{day:02}", &format!("{:02}", self.day))
+            .replace("{day}", &self.day.to_string())
     }
 
     /// Consumes the date to get an URL
     pub fn request_url(&self) -> String {
-        format!(
-            "https://adventofcode.com/{}/day/{}/input",
-            self.year, self.day
-        )
+        http::url(&format!("/{}/day/{}/input", self.year, self.day))
+    }
+
+    /// Gets the URL of the puzzle description
+    pub fn puzzle_url(&self) -> String {
+        http::url(&format!("/{}/day/{}", self.year, self.day))
+    }
+
+    /// Gets the URL used to submit answers
+    pub fn submit_url(&self) -> String {
+        http::url(&format!("/{}/day/{}/answer", self.year, self.day))
     }
 }
@@ -0,0 +1,95 @@
+use aoc_runner_internal::Day;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A selection of days, parsed from a single day (`7`), an inclusive range (`1..10`),
+/// a list (`1,3,5`), or a mix of them (`1..5,7`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days(Vec<Day>);
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = Vec::new();
+
+        for item in s.split(',').map(str::trim) {
+            match item.split_once("..") {
+                Some((start, end)) => {
+                    let start: Day = start.parse()?;
+                    let end: Day = end.trim_start_matches('=').parse()?;
+                    if start > end {
+                        return Err(format!("Invalid range of days: {}", item));
+                    }
+                    days.extend((start.0..=end.0).map(Day));
+                }
+                None => days.push(item.parse()?),
+            }
+        }
+
+        days.sort();
+        days.dedup();
+        Ok(Days(days))
+    }
+}
+
+impl From<Day> for Days {
+    fn from(day: Day) -> Self {
+        Days(vec![day])
+    }
+}
+
+impl Deref for Days {
+    type Target = [Day];
+
+    fn deref(&self) -> &[Day] {
+        &self.0
+    }
+}
+
+impl Days {
+    /// Gets the day, if a single one is selected
+    pub fn single(&self) -> Option<Day> {
+        match self.0[..] {
+            [day] => Some(day),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(days: &[u8]) -> Days {
+        Days(days.iter().copied().map(Day).collect())
+    }
+
+    #[test]
+    fn parse_days() {
+        assert_eq!("7".parse(), Ok(days(&[7])));
+        assert_eq!("day7".parse(), Ok(days(&[7])));
+        assert_eq!("1..4".parse(), Ok(days(&[1, 2, 3, 4])));
+        assert_eq!("1..=4".parse(), Ok(days(&[1, 2, 3, 4])));
+        assert_eq!("5, 1,3".parse(), Ok(days(&[1, 3, 5])));
+        assert_eq!("1..3,7,2".parse(), Ok(days(&[1, 2, 3, 7])));
+        assert_eq!("4..4".parse(), Ok(days(&[4])));
+    }
+
+    #[test]
+    fn parse_invalid_days() {
+        assert!("".parse::<Days>().is_err());
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("5..2".parse::<Days>().is_err());
+        assert!("1..".parse::<Days>().is_err());
+        assert!("1,,3".parse::<Days>().is_err());
+    }
+
+    #[test]
+    fn single_day() {
+        assert_eq!(days(&[7]).single(), Some(Day(7)));
+        assert_eq!(days(&[1, 2]).single(), None);
+        assert_eq!(Days::from(Day(3)).single(), Some(Day(3)));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::color;
+use crate::date::AOCDate;
+use crate::http::{self, Endpoint};
+use aoc_runner_internal::DayParts;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// The outcome of a diagnostic, with an actionable message when something is wrong
+pub enum Outcome {
+    Ok(String),
+    Warning(String),
+    Error(String),
+}
+
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    pub fn new(name: &'static str, outcome: Outcome) -> Self {
+        Check { name, outcome }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.outcome, Outcome::Error(_))
+    }
+
+    pub fn print(&self) {
+        match &self.outcome {
+            Outcome::Ok(msg) => {
+                println!("[{}] {} : {}", color::paint(color::GREEN, " ok "), self.name, msg)
+            }
+            Outcome::Warning(msg) => {
+                println!("[{}] {} : {}", color::paint(color::YELLOW, "warn"), self.name, msg)
+            }
+            Outcome::Error(msg) => {
+                println!("[{}] {} : {}", color::paint(color::RED, "fail"), self.name, msg)
+            }
+        }
+    }
+}
+
+pub fn network() -> Outcome {
+    match http::get(&Endpoint::PAGE, &http::url("/"), None) {
+        Ok(response) if response.status.is_success() => {
+            Outcome::Ok("adventofcode.com is reachable".into())
+        }
+        Ok(response) => Outcome::Error(format!(
+            "adventofcode.com answered with status {}, try again later",
+            response.status
+        )),
+        Err(e) => Outcome::Error(format!(
+            "adventofcode.com is not reachable ({}), check your internet connection",
+            e
+        )),
+    }
+}
+
+pub fn session(token: Result<String, String>, year: i32) -> Outcome {
+    let token = match token {
+        Ok(token) => token,
+        Err(_) => {
+            return Outcome::Error(
+                "No session token, set it using `cargo aoc credentials {token}`".into(),
+            )
+        }
+    };
+
+    // The event page shows the login links only to anonymous users
+    let response = http::get(
+        &Endpoint::PAGE,
+        &http::url(&format!("/{}", year)),
+        Some(&token),
+    );
+
+    match response.map(|r| r.body) {
+        Ok(page) if page.contains("[Log Out]") => Outcome::Ok("the session token is valid".into()),
+        Ok(_) => Outcome::Error(
+            "the session token is not valid anymore, \
+             copy it again from your browser and run `cargo aoc credentials {token}`"
+                .into(),
+        ),
+        Err(e) => Outcome::Warning(format!("could not check the session token ({})", e)),
+    }
+}
+
+pub fn dependencies() -> Outcome {
+    let cargo = match fs::read_to_string("Cargo.toml").map(|c| c.parse::<toml::Value>()) {
+        Ok(Ok(cargo)) => cargo,
+        _ => {
+            return Outcome::Error(
+                "no valid Cargo.toml found, run cargo aoc from the root of your crate".into(),
+            )
+        }
+    };
+
+    let missing: Vec<_> = ["aoc-runner", "aoc-runner-derive"]
+        .into_iter()
+        .filter(|dep| cargo.get("dependencies").and_then(|d| d.get(dep)).is_none())
+        .collect();
+
+    if missing.is_empty() {
+        Outcome::Ok("aoc-runner & aoc-runner-derive are in the dependencies".into())
+    } else {
+        Outcome::Error(format!(
+            "missing dependencies, run `cargo add {}`",
+            missing.join(" ")
+        ))
+    }
+}
+
+pub fn metadata(src_dir: &Path, last_change: SystemTime) -> Outcome {
+    let generated = fs::metadata("target/aoc/completed.json").and_then(|m| m.modified());
+
+    match (DayParts::load(), generated) {
+        (Ok(_), Ok(generated)) if generated >= last_change => {
+            Outcome::Ok("the runner metadata is up to date".into())
+        }
+        (Ok(_), Ok(_)) => Outcome::Warning(format!(
+            "{} changed since the runner metadata was generated, \
+             it will be updated on the next `cargo aoc` run",
+            src_dir.display()
+        )),
+        _ => Outcome::Error(
+            "no runner metadata, make sure `aoc_lib! { year = XXXX }` is at the end of lib.rs, \
+             and run `cargo check`"
+                .into(),
+        ),
+    }
+}
+
+pub fn year(year: Option<u32>, current_year: i32) -> Outcome {
+    match year {
+        Some(year) if year < 2015 || year as i32 > current_year => Outcome::Error(format!(
+            "{} is not a valid Advent of Code year, fix `aoc_lib! {{ year = XXXX }}` in lib.rs",
+            year
+        )),
+        Some(year) => Outcome::Ok(format!("the project is configured for {}", year)),
+        None => Outcome::Warning("unknown, the runner metadata is missing".into()),
+    }
+}
+
+pub fn input_directory(date: AOCDate) -> Outcome {
+    let dir = date.directory();
+    let probe = Path::new(&dir).join(".cargo-aoc-doctor");
+
+    match fs::create_dir_all(&dir).and_then(|_| fs::write(&probe, "")) {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            Outcome::Ok(format!("{} is writable", dir))
+        }
+        Err(e) => Outcome::Error(format!(
+            "{} is not writable ({}), check the permissions of the directory",
+            dir, e
+        )),
+    }
+}
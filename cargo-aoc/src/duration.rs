@@ -0,0 +1,35 @@
+use serde::{de, Deserialize, Deserializer};
+use std::time::Duration;
+
+/// Parses a duration given on the command line, like `30s`, `500ms`, `2m` or `1h`.
+/// A number without unit is a number of seconds.
+pub fn parse(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("Invalid duration: {}", s))?;
+    let seconds = match unit.trim() {
+        "ns" => value / 1e9,
+        "us" | "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "" | "s" => value,
+        "m" | "min" => value * 60.,
+        "h" => value * 3600.,
+        unit => return Err(format!("Invalid duration unit: {}", unit)),
+    };
+
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Deserializes a duration of aoc.toml, written like on the command line
+pub fn deserialize_option<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse(&s).map(Some).map_err(de::Error::custom)
+}
@@ -0,0 +1,114 @@
+//! Encrypts the downloaded inputs, so that they can be committed without publishing them.
+//!
+//! With an `[encryption]` table in aoc.toml, the inputs are stored encrypted in the input
+//! directory, like `input/2023/day1.txt.age`, and decrypted in `target/aoc/inputs` to run the
+//! solutions. The encryption itself is done by `age` or `gpg`.
+
+use crate::config::{Config, EncryptionConfig, EncryptionTool};
+use std::error;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Gets the settings of the encryption, if the inputs are encrypted
+pub fn config() -> Option<&'static EncryptionConfig> {
+    Config::get().encryption.as_ref()
+}
+
+impl EncryptionTool {
+    /// The extension of the encrypted files
+    pub fn extension(self) -> &'static str {
+        match self {
+            EncryptionTool::Age => "age",
+            EncryptionTool::Gpg => "gpg",
+        }
+    }
+}
+
+/// Encrypts an input to a file
+pub fn encrypt(
+    config: &EncryptionConfig,
+    input: &[u8],
+    path: &Path,
+) -> Result<(), Box<dyn error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let path = path.display().to_string();
+    match config.tool {
+        EncryptionTool::Age => run(
+            "age",
+            &[
+                "--encrypt",
+                "--recipient",
+                &config.recipient,
+                "--output",
+                &path,
+            ],
+            Some(input),
+        )?,
+        EncryptionTool::Gpg => run(
+            "gpg",
+            &[
+                "--batch",
+                "--yes",
+                "--encrypt",
+                "--recipient",
+                &config.recipient,
+                "--output",
+                &path,
+            ],
+            Some(input),
+        )?,
+    };
+    Ok(())
+}
+
+/// Decrypts an encrypted input
+pub fn decrypt(config: &EncryptionConfig, path: &Path) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    let path = path.display().to_string();
+    match config.tool {
+        EncryptionTool::Age => {
+            let identity = config.identity.as_deref().ok_or(
+                "Set the key used to decrypt the inputs with `identity` in the [encryption] \
+                 table of aoc.toml",
+            )?;
+            run("age", &["--decrypt", "--identity", identity, &path], None)
+        }
+        // gpg finds the key by itself
+        EncryptionTool::Gpg => run("gpg", &["--batch", "--quiet", "--decrypt", &path], None),
+    }
+}
+
+/// Runs an encryption tool, and returns its standard output
+fn run(
+    program: &str,
+    args: &[&str],
+    input: Option<&[u8]>,
+) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Could not run {} to encrypt the inputs: {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Some(input) = input {
+            stdin.write_all(input)?;
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(output.stdout)
+}
@@ -21,3 +21,20 @@ impl Display for CouldNotLoadDayParts {
 impl Error for CouldNotLoadDayParts {
 
 }
+
+/// A puzzle that isn't unlocked yet, which can be requested again later
+pub struct NotUnlocked(pub String);
+
+impl Debug for NotUnlocked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}", self))
+    }
+}
+
+impl Display for NotUnlocked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for NotUnlocked {}
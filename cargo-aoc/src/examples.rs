@@ -0,0 +1,126 @@
+use crate::config::Config;
+use crate::puzzle;
+use aoc_runner_internal::{Day, Part};
+use serde::Deserialize;
+use std::error;
+use std::fs;
+use std::path::PathBuf;
+
+/// An example of the puzzle description, with its expected answers
+#[derive(Debug, Clone, Deserialize)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part.0 {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ExamplesFile {
+    #[serde(default, rename = "example")]
+    examples: Vec<Example>,
+}
+
+/// Gets the path of the examples of a day : `{input_dir}/{year}/examples/day{day}.toml`
+pub fn path(year: i32, day: Day) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/{}/examples/day{}.toml",
+        Config::get().input_dir(),
+        year,
+        day.0
+    ))
+}
+
+/// Loads the examples of a day, if there is any
+pub fn load(year: i32, day: Day) -> Result<Vec<Example>, Box<dyn error::Error>> {
+    let path = path(year, day);
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file: ExamplesFile = toml::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    Ok(file.examples)
+}
+
+/// Marks the examples files written by cargo-aoc, which are updated once the part 2 is unlocked
+const EXTRACTED_HEADER: &str =
+    "# Extracted from the puzzle description by cargo-aoc, check them before relying on them.\n\
+     # Remove this line to keep your changes.\n";
+
+/// Guesses the example of a puzzle from its description : the first code block is the input,
+/// and the answer of each part is the last highlighted code of its description.
+pub fn extract(description: &str) -> Option<Example> {
+    let articles = puzzle::articles(description);
+    let first = articles.first()?;
+
+    let start = first.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + first[start..].find("</code></pre>")?;
+    let input = puzzle::text(&first[start..end]);
+
+    Some(Example {
+        input: input.trim_end_matches('\n').to_string(),
+        part1: last_answer(first),
+        part2: articles.get(1).and_then(|article| last_answer(article)),
+    })
+}
+
+/// Finds the last highlighted code of a description, written `<code><em>` or `<em><code>`
+fn last_answer(article: &str) -> Option<String> {
+    let (start, tag) = ["<code><em>", "<em><code>"]
+        .into_iter()
+        .filter_map(|tag| Some((article.rfind(tag)?, tag)))
+        .max()?;
+    let start = start + tag.len();
+    let end = start + article[start..].find('<')?;
+
+    Some(puzzle::text(&article[start..end]))
+}
+
+/// Writes the example extracted from the description of a puzzle, unless the examples of the day
+/// were written by hand. Returns whether the example was written.
+pub fn save_extracted(
+    year: i32,
+    day: Day,
+    description: &str,
+) -> Result<bool, Box<dyn error::Error>> {
+    let path = path(year, day);
+    if let Ok(content) = fs::read_to_string(&path) {
+        if !content.starts_with(EXTRACTED_HEADER) {
+            return Ok(false);
+        }
+    }
+    let Some(example) = extract(description) else {
+        return Ok(false);
+    };
+
+    let mut content = format!("{}\n[[example]]\n", EXTRACTED_HEADER);
+    if example.input.contains("'''") {
+        content += &format!("input = {}\n", toml::Value::from(example.input));
+    } else {
+        content += &format!("input = '''\n{}'''\n", example.input);
+    }
+    for (part, answer) in [("part1", example.part1), ("part2", example.part2)] {
+        if let Some(answer) = answer {
+            content += &format!("{} = {}\n", part, toml::Value::from(answer));
+        }
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, content)?;
+
+    Ok(true)
+}
@@ -0,0 +1,86 @@
+use clap::Args;
+use std::sync::OnceLock;
+
+/// The features of the project enabled when building the solutions, like cargo does
+#[derive(Args, Debug, Clone, Default)]
+pub struct Features {
+    /// Space or comma separated list of features of the project to activate.
+    #[clap(short = 'F', long, global = true)]
+    features: Vec<String>,
+
+    /// Activates all the features of the project.
+    #[clap(long, global = true)]
+    all_features: bool,
+
+    /// Does not activate the `default` feature of the project.
+    #[clap(long, global = true)]
+    no_default_features: bool,
+}
+
+static FEATURES: OnceLock<Features> = OnceLock::new();
+
+/// Sets the features of the project, must be called before building anything
+pub fn init(features: Features) {
+    let _ = FEATURES.set(features);
+}
+
+fn get() -> &'static Features {
+    FEATURES.get_or_init(Features::default)
+}
+
+/// Gets the list of the features, split like cargo does
+fn list() -> Vec<&'static str> {
+    get()
+        .features
+        .iter()
+        .flat_map(|f| f.split([' ', ',']))
+        .filter(|f| !f.is_empty())
+        .collect()
+}
+
+/// Gets the arguments given to cargo when building the project, cargo-aoc takes the same
+pub fn cargo_args() -> Vec<String> {
+    let mut args = Vec::new();
+    let list = list();
+    if !list.is_empty() {
+        args.extend(["--features".to_string(), list.join(",")]);
+    }
+    if get().all_features {
+        args.push("--all-features".to_string());
+    }
+    if get().no_default_features {
+        args.push("--no-default-features".to_string());
+    }
+    args
+}
+
+/// Gets the fields added to the dependency on the project, in the manifest of a generated crate.
+///
+/// The generated crates depend on the project, so the features are enabled on this dependency.
+pub fn dependency() -> String {
+    let mut fields = String::new();
+    let mut list: Vec<String> = list().iter().map(|f| format!("{:?}", f)).collect();
+    if get().all_features {
+        // Cargo can't enable all the features of a dependency, they are read from the project
+        list = project_features().iter().map(|f| format!("{:?}", f)).collect();
+    }
+    if !list.is_empty() {
+        fields.push_str(&format!(", features = [{}]", list.join(", ")));
+    }
+    if get().no_default_features {
+        fields.push_str(", default-features = false");
+    }
+    fields
+}
+
+/// Reads the names of the features declared in the Cargo.toml of the project
+fn project_features() -> Vec<String> {
+    std::fs::read_to_string("Cargo.toml")
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|cargo| {
+            let features = cargo.get("features")?.as_table()?;
+            Some(features.keys().cloned().collect())
+        })
+        .unwrap_or_default()
+}
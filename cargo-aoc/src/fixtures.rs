@@ -0,0 +1,95 @@
+//! Records the HTTP interactions to fixtures, and replays them later, to test the downloads and
+//! the submissions without reaching adventofcode.com.
+//!
+//! `CARGO_AOC_HTTP_RECORD={dir}` writes every response to `{dir}`, and `CARGO_AOC_HTTP_REPLAY={dir}`
+//! answers the requests from it, without any network access. The session token is not part of a
+//! fixture, so they can be shared.
+
+use crate::http::Response;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::error;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// The environment variable holding the directory the interactions are recorded to
+pub const RECORD_ENV: &str = "CARGO_AOC_HTTP_RECORD";
+
+/// The environment variable holding the directory the interactions are replayed from
+pub const REPLAY_ENV: &str = "CARGO_AOC_HTTP_REPLAY";
+
+/// An HTTP interaction, as stored in a fixture
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    form: Vec<(String, String)>,
+    response: Response,
+}
+
+/// Tells if the requests are answered from the fixtures
+pub fn replaying() -> bool {
+    env::var_os(REPLAY_ENV).is_some()
+}
+
+/// Answers a request from its fixture
+pub fn replay(
+    method: &str,
+    url: &str,
+    form: &[(&str, &str)],
+) -> Result<Response, Box<dyn error::Error>> {
+    let dir = env::var(REPLAY_ENV)?;
+    let path = path(&dir, method, url, form);
+    let content = fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "No recorded response for {} {} ({}: {})",
+            method,
+            url,
+            path.display(),
+            e
+        )
+    })?;
+    let fixture: Fixture = serde_json::from_str(&content)?;
+    Ok(fixture.response)
+}
+
+/// Stores a response in its fixture, when recording
+pub fn record(
+    method: &str,
+    url: &str,
+    form: &[(&str, &str)],
+    response: &Response,
+) -> Result<(), Box<dyn error::Error>> {
+    let Ok(dir) = env::var(RECORD_ENV) else {
+        return Ok(());
+    };
+    fs::create_dir_all(&dir)?;
+
+    let fixture = Fixture {
+        method: method.to_string(),
+        url: url.to_string(),
+        form: form
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        response: response.clone(),
+    };
+    fs::write(
+        path(&dir, method, url, form),
+        serde_json::to_string_pretty(&fixture)?,
+    )?;
+    Ok(())
+}
+
+/// Gets the path of the fixture of a request : `{dir}/{method}-{hash}.json`
+fn path(dir: &str, method: &str, url: &str, form: &[(&str, &str)]) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    (method, url, form).hash(&mut hasher);
+    PathBuf::from(dir).join(format!(
+        "{}-{:016x}.json",
+        method.to_lowercase(),
+        hasher.finish()
+    ))
+}
@@ -0,0 +1,654 @@
+//! Every request of cargo-aoc goes through here, to be a good citizen of adventofcode.com : the
+//! requests carry a User-Agent telling who sends them, are spaced out per kind of request, and the
+//! pages that may be asked again soon are cached.
+
+use crate::config::Config;
+use crate::fixtures;
+use directories::ProjectDirs;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::header::{COOKIE, USER_AGENT};
+use reqwest::{NoProxy, Proxy, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::env;
+use std::error;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The User-Agent of the requests, with a way to contact the maintainers, as asked by the
+/// Advent of Code
+pub const CARGO_AOC_USER_AGENT: &str = concat!(
+    "github.com/gobanos/cargo-aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " by gregory.obanos@gmail.com"
+);
+
+/// The environment variable holding the address of the Advent of Code, used instead of
+/// `base_url` in aoc.toml
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// The address of the Advent of Code, when it's not overridden
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The proxy given on the command line
+static PROXY: OnceLock<Option<String>> = OnceLock::new();
+
+/// Forbids every request, given on the command line
+static OFFLINE: OnceLock<bool> = OnceLock::new();
+
+pub fn init(proxy: Option<String>, offline: bool) {
+    let _ = PROXY.set(proxy);
+    let _ = OFFLINE.set(offline);
+}
+
+/// Tells if the network must not be used, with `--offline` or `offline = true` in aoc.toml
+pub fn offline() -> bool {
+    OFFLINE.get().copied().unwrap_or(false) || Config::get().offline
+}
+
+/// Gets the URL of a page of the Advent of Code, from its path like `/2023/day/1`
+pub fn url(path: &str) -> String {
+    let base = env::var(BASE_URL_ENV)
+        .ok()
+        .or_else(|| Config::get().base_url.clone())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+/// Fails when a request would be sent in offline mode
+fn check_online(url: &str) -> Result<(), String> {
+    if offline() {
+        return Err(format!(
+            "Can't request {} in offline mode (remove --offline to use the network)",
+            url
+        ));
+    }
+    Ok(())
+}
+
+/// A kind of request, throttled and cached on its own
+#[derive(Debug)]
+pub struct Endpoint {
+    /// The name of the endpoint, in the throttling state
+    name: &'static str,
+    /// The minimum time between two requests
+    interval: Duration,
+    /// How long the responses are reused, if they are cached
+    ttl: Option<Duration>,
+    timeout: Duration,
+    /// Retries the requests failing because of the network or the server
+    retry: bool,
+    /// Answers a malformed or expired session token with a 500 status, which isn't retried
+    rejects_session: bool,
+}
+
+impl Endpoint {
+    /// The puzzle inputs, saved in the input directory once downloaded
+    pub const INPUT: Endpoint = Endpoint {
+        name: "input",
+        interval: Duration::from_secs(1),
+        ttl: None,
+        timeout: Duration::from_secs(30),
+        retry: true,
+        rejects_session: true,
+    };
+    /// The pages of adventofcode.com which must be fresh, like the puzzle descriptions
+    /// (saved by `cargo aoc puzzle`) or the checks of `cargo aoc doctor`
+    pub const PAGE: Endpoint = Endpoint {
+        name: "page",
+        interval: Duration::from_secs(1),
+        ttl: None,
+        timeout: Duration::from_secs(10),
+        retry: true,
+        rejects_session: false,
+    };
+    /// The puzzle descriptions downloaded for their titles only, given up quickly since the
+    /// titles are only a nicety
+    pub const TITLE: Endpoint = Endpoint {
+        name: "page",
+        interval: Duration::from_secs(1),
+        ttl: None,
+        timeout: Duration::from_secs(3),
+        retry: false,
+        rejects_session: false,
+    };
+    /// The event page, read for the name of the account
+    pub const ACCOUNT: Endpoint = Endpoint {
+        name: "account",
+        interval: Duration::from_secs(1),
+        ttl: Some(Duration::from_secs(5 * 60)),
+        timeout: Duration::from_secs(10),
+        retry: false,
+        rejects_session: false,
+    };
+    /// The submitted answers, never cached
+    pub const ANSWER: Endpoint = Endpoint {
+        name: "answer",
+        interval: Duration::from_secs(5),
+        ttl: None,
+        timeout: Duration::from_secs(30),
+        retry: false,
+        rejects_session: true,
+    };
+    /// The personal stats, which the Advent of Code asks not to fetch more than every 15 minutes
+    pub const STATS: Endpoint = Endpoint {
+        name: "stats",
+        interval: Duration::from_secs(1),
+        ttl: Some(Duration::from_secs(15 * 60)),
+        timeout: Duration::from_secs(10),
+        retry: false,
+        rejects_session: false,
+    };
+    /// The crates.io API, for the last released version
+    pub const CRATES_IO: Endpoint = Endpoint {
+        name: "crates.io",
+        interval: Duration::from_secs(1),
+        ttl: None,
+        timeout: Duration::from_secs(5),
+        retry: false,
+        rejects_session: false,
+    };
+}
+
+/// A response, read at once to be cached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    #[serde(with = "status_code")]
+    pub status: StatusCode,
+    pub body: String,
+}
+
+/// Sends a GET request, with the session token if any, or reuses a cached response
+pub fn get(
+    endpoint: &Endpoint,
+    url: &str,
+    token: Option<&str>,
+) -> Result<Response, Box<dyn error::Error>> {
+    if fixtures::replaying() {
+        return fixtures::replay("GET", url, &[]);
+    }
+
+    let cache = endpoint
+        .ttl
+        .and_then(|ttl| Some((cache_path(url, token)?, ttl)));
+    if let Some((path, ttl)) = &cache {
+        if let Some(response) = cached(path, *ttl) {
+            return Ok(response);
+        }
+    }
+    check_online(url)?;
+
+    let attempts = if endpoint.retry {
+        Config::get().download_attempts.max(1)
+    } else {
+        1
+    };
+    let response = get_retrying(client()?, endpoint, url, token, attempts)?;
+    fixtures::record("GET", url, &[], &response)?;
+
+    if let Some((path, _)) = &cache {
+        if response.status == StatusCode::OK {
+            let _ = fs::write(path, serde_json::to_string(&response)?);
+        }
+    }
+    Ok(response)
+}
+
+/// Sends a GET request, again after a backoff while it fails in a way that could succeed later,
+/// up to a number of attempts
+fn get_retrying(
+    client: &Client,
+    endpoint: &Endpoint,
+    url: &str,
+    token: Option<&str>,
+    attempts: u32,
+) -> Result<Response, Box<dyn error::Error>> {
+    let mut attempt = 1;
+    loop {
+        let result = send(endpoint, with_token(client.get(url), token));
+        let failure = match &result {
+            Ok(response)
+                if endpoint.rejects_session
+                    && response.status == StatusCode::INTERNAL_SERVER_ERROR =>
+            {
+                None
+            }
+            Ok(response) if response.status.is_server_error() => {
+                Some(format!("{} answered {}", url, response.status))
+            }
+            Err(e) if e.downcast_ref::<reqwest::Error>().is_some_and(is_transient) => {
+                Some(e.to_string())
+            }
+            _ => None,
+        };
+        match failure {
+            Some(failure) if attempt < attempts => {
+                let delay = backoff(attempt);
+                eprintln!(
+                    "{}, trying again in {:.1}s ({}/{})",
+                    failure,
+                    delay.as_secs_f64(),
+                    attempt + 1,
+                    attempts
+                );
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            _ => return result,
+        }
+    }
+}
+
+/// Sends a form in a POST request, with the session token
+pub fn post(
+    endpoint: &Endpoint,
+    url: &str,
+    token: &str,
+    form: &[(&str, &str)],
+) -> Result<Response, Box<dyn error::Error>> {
+    if fixtures::replaying() {
+        return fixtures::replay("POST", url, form);
+    }
+    check_online(url)?;
+
+    let response = send(
+        endpoint,
+        with_token(client()?.post(url), Some(token)).form(form),
+    )?;
+    fixtures::record("POST", url, form, &response)?;
+    Ok(response)
+}
+
+/// Gets the client sending every request.
+///
+/// The proxy given on the command line or in aoc.toml is used first, `NO_PROXY` still applying
+/// to it. Otherwise, the `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` environment variables are
+/// read by reqwest.
+fn client() -> Result<&'static Client, Box<dyn error::Error>> {
+    static CLIENT: OnceLock<Result<Client, String>> = OnceLock::new();
+
+    let client = CLIENT.get_or_init(|| {
+        let proxy = PROXY
+            .get()
+            .cloned()
+            .flatten()
+            .or_else(|| Config::get().proxy.clone());
+        let mut builder = Client::builder();
+        if let Some(proxy) = proxy {
+            let proxy = Proxy::all(&proxy)
+                .map_err(|e| format!("Invalid proxy {}: {}", proxy, e))?
+                .no_proxy(NoProxy::from_env());
+            builder = builder.proxy(proxy);
+        }
+        builder.build().map_err(|e| e.to_string())
+    });
+    Ok(client.as_ref().map_err(|e| e.clone())?)
+}
+
+fn with_token(request: RequestBuilder, token: Option<&str>) -> RequestBuilder {
+    let request = request.header(USER_AGENT, CARGO_AOC_USER_AGENT);
+    match token {
+        Some(token) => request.header(COOKIE, format!("session={}", token)),
+        None => request,
+    }
+}
+
+fn send(endpoint: &Endpoint, request: RequestBuilder) -> Result<Response, Box<dyn error::Error>> {
+    throttle(endpoint);
+
+    let response = request.timeout(endpoint.timeout).send()?;
+    Ok(Response {
+        status: response.status(),
+        body: response.text()?,
+    })
+}
+
+/// Tells if a request failed because of the network, and could succeed later : a timeout, a
+/// failed connection, or one reset while sending the request or reading the response. The other
+/// errors, like an invalid URL, would fail again.
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+}
+
+/// Gets the time to wait before an attempt, doubling after each one up to 30 seconds.
+///
+/// A random part of it is dropped, so that everyone waiting for the unlock doesn't retry at once.
+fn backoff(attempt: u32) -> Duration {
+    let delay = (1000u64 << attempt.min(6).saturating_sub(1)).min(30_000);
+    let jitter = now_nanos() % (delay / 2 + 1);
+    Duration::from_millis(delay - jitter)
+}
+
+/// Waits until the minimum time since the last request to an endpoint is over.
+///
+/// The time of the last requests is shared by every cargo-aoc process, through a file of the
+/// cache directory, and each request reserves its slot before waiting for it, so that the
+/// threads of a process are spaced out too. The throttling is skipped if it can't be read, or if
+/// the endpoint has no minimum time between requests.
+fn throttle(endpoint: &Endpoint) {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    if endpoint.interval.is_zero() {
+        return;
+    }
+
+    let Some(path) = cache_dir().map(|dir| dir.join("last-requests.json")) else {
+        return;
+    };
+    let slot = {
+        let _lock = LOCK.lock().unwrap();
+        match reserve(&path, endpoint.name, endpoint.interval, now_millis()) {
+            Some(slot) => slot,
+            None => return,
+        }
+    };
+
+    let now = now_millis();
+    if slot > now {
+        thread::sleep(Duration::from_millis(slot - now));
+    }
+}
+
+/// Reserves the slot of the next request to an endpoint, in milliseconds since the epoch, in the
+/// file of the last requests.
+///
+/// The other processes wait for the lock of the file while it's read and written, and the new
+/// times replace it at once, so that it's never read half written.
+fn reserve(path: &Path, name: &str, interval: Duration, now: u64) -> Option<u64> {
+    let lock = fs::File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("lock"))
+        .ok()?;
+    lock.lock().ok()?;
+
+    let mut last_requests: BTreeMap<String, u64> = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let slot = last_requests
+        .get(name)
+        .map_or(now, |last| last + interval.as_millis() as u64)
+        .max(now);
+    last_requests.insert(name.to_string(), slot);
+
+    let written = path.with_extension(format!("{}.json", std::process::id()));
+    if let Ok(content) = serde_json::to_string(&last_requests) {
+        if fs::write(&written, content).is_ok() && fs::rename(&written, path).is_err() {
+            let _ = fs::remove_file(&written);
+        }
+    }
+    Some(slot)
+}
+
+/// Gets a cached response, if it's recent enough
+fn cached(path: &PathBuf, ttl: Duration) -> Option<Response> {
+    let age = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+    if age > ttl {
+        return None;
+    }
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Gets the path of the cached response of a request, a token having its own responses
+fn cache_path(url: &str, token: Option<&str>) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    (url, token).hash(&mut hasher);
+
+    let dir = cache_dir()?.join("responses");
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir.join(format!("{:016x}.json", hasher.finish())))
+}
+
+/// Gets the cache directory of cargo-aoc, creating it if needed
+pub fn cache_dir() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("com.github", "gobanos", "cargo-aoc")?;
+    fs::create_dir_all(dirs.cache_dir()).ok()?;
+    Some(dirs.cache_dir().to_path_buf())
+}
+
+fn now_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos() as u64)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Stores the status codes as numbers
+mod status_code {
+    use reqwest::StatusCode;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(status: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(status.as_u16())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StatusCode, D::Error> {
+        StatusCode::from_u16(u16::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Gets a path in the temporary directory, unique to a test
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("cargo-aoc-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn cached_response() {
+        let path = temp_path("cached-response");
+        let response = Response {
+            status: StatusCode::OK,
+            body: "<article>puzzle</article>".to_string(),
+        };
+        fs::write(&path, serde_json::to_string(&response).unwrap()).unwrap();
+
+        let hit = cached(&path, Duration::from_secs(3600));
+        let _ = fs::remove_file(&path);
+        let hit = hit.unwrap();
+        assert_eq!(hit.status, StatusCode::OK);
+        assert_eq!(hit.body, response.body);
+    }
+
+    #[test]
+    fn expired_response() {
+        let path = temp_path("expired-response");
+        fs::write(&path, r#"{"status":200,"body":""}"#).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(120);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(old))
+            .unwrap();
+
+        let hit = cached(&path, Duration::from_secs(60));
+        let _ = fs::remove_file(&path);
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn invalid_or_missing_response() {
+        let path = temp_path("invalid-response");
+        fs::write(&path, "not json").unwrap();
+        let hit = cached(&path, Duration::from_secs(3600));
+        let _ = fs::remove_file(&path);
+
+        assert!(hit.is_none());
+        assert!(cached(&temp_path("missing-response"), Duration::from_secs(3600)).is_none());
+    }
+
+    /// An endpoint retried, and not throttled, for the requests to a local server
+    const LOCAL: Endpoint = Endpoint {
+        name: "local",
+        interval: Duration::ZERO,
+        ttl: None,
+        timeout: Duration::from_secs(5),
+        retry: true,
+        rejects_session: false,
+    };
+
+    /// Serves a response on a local port, after resetting a number of connections, and returns
+    /// its URL with the count of the connections
+    fn serve(resets: usize, response: &'static str) -> (String, Arc<AtomicUsize>) {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2023/day/1/input", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let count = connections.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                // Closing a connection without reading the request resets it
+                if count.fetch_add(1, Ordering::SeqCst) >= resets {
+                    let _ = stream.read(&mut [0; 4096]);
+                    let _ = stream.write_all(response.as_bytes());
+                }
+            }
+        });
+        (url, connections)
+    }
+
+    fn local_client() -> Client {
+        Client::builder().no_proxy().build().unwrap()
+    }
+
+    #[test]
+    fn reset_connection_is_retried() {
+        let (url, connections) = serve(
+            1,
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\ninput",
+        );
+
+        let response = get_retrying(&local_client(), &LOCAL, &url, None, 3).unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.body, "input");
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn reset_connection_fails_after_the_attempts() {
+        let (url, connections) = serve(2, "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+
+        let e = get_retrying(&local_client(), &LOCAL, &url, None, 2).unwrap_err();
+        assert!(e.downcast_ref::<reqwest::Error>().is_some_and(is_transient));
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn server_error_is_retried() {
+        let (url, connections) = serve(
+            0,
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+
+        let response = get_retrying(&local_client(), &LOCAL, &url, None, 2).unwrap();
+        assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn rejected_session_is_not_retried() {
+        let endpoint = Endpoint {
+            rejects_session: true,
+            ..LOCAL
+        };
+        let (url, connections) = serve(
+            0,
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+
+        let response = get_retrying(&local_client(), &endpoint, &url, Some("0"), 3).unwrap();
+        assert_eq!(response.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn reserve_spaced_slots() {
+        let path = temp_path("last-requests");
+        let interval = Duration::from_secs(1);
+
+        assert_eq!(reserve(&path, "input", interval, 10_000), Some(10_000));
+        assert_eq!(reserve(&path, "input", interval, 10_200), Some(11_000));
+        assert_eq!(reserve(&path, "page", interval, 10_200), Some(10_200));
+        assert_eq!(reserve(&path, "input", interval, 15_000), Some(15_000));
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("lock"));
+    }
+
+    #[test]
+    fn reserve_after_a_damaged_file() {
+        let path = temp_path("damaged-requests");
+        fs::write(&path, r#"{"input": 10"#).unwrap();
+
+        let slot = reserve(&path, "input", Duration::from_secs(1), 5_000);
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("lock"));
+
+        assert_eq!(slot, Some(5_000));
+        assert_eq!(content, r#"{"input":5000}"#);
+    }
+
+    #[test]
+    fn reserve_concurrently() {
+        let path = temp_path("concurrent-requests");
+        let interval = Duration::from_secs(1);
+
+        // Without the process-wide lock of `throttle`, like separate processes
+        let mut slots: Vec<_> = thread::scope(|s| {
+            let threads: Vec<_> = (0..8)
+                .map(|_| s.spawn(|| reserve(&path, "input", interval, 10_000).unwrap()))
+                .collect();
+            threads.into_iter().map(|t| t.join().unwrap()).collect()
+        });
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("lock"));
+
+        slots.sort();
+        let expected: Vec<_> = (0..8).map(|i| 10_000 + i * 1000).collect();
+        assert_eq!(slots, expected);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_a_limit() {
+        // The jitter drops up to half of the delay
+        for (attempt, delay) in [
+            (1, 1000),
+            (2, 2000),
+            (3, 4000),
+            (5, 16000),
+            (6, 30000),
+            (20, 30000),
+        ] {
+            let waited = backoff(attempt).as_millis() as u64;
+            assert!(
+                (delay / 2..=delay).contains(&waited),
+                "attempt {}: {}ms",
+                attempt,
+                waited
+            );
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// The service the session tokens are stored under, an account per session profile
+const SERVICE: &str = "cargo-aoc";
+
+/// Loads the Windows Credential Manager, in PowerShell
+const VAULT: &str = "[void][Windows.Security.Credentials.PasswordVault,\
+                     Windows.Security.Credentials,ContentType=WindowsRuntime]; \
+                     $vault = New-Object Windows.Security.Credentials.PasswordVault;";
+
+/// The keyring of a system, and the tool accessing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    /// The Keychain of macOS, with `security`
+    Keychain,
+    /// The Windows Credential Manager, with PowerShell
+    CredentialManager,
+    /// The Secret Service of Linux, with `secret-tool`
+    SecretService,
+}
+
+/// A run of the tool of a keyring
+#[derive(Debug, PartialEq, Eq)]
+struct Invocation {
+    program: &'static str,
+    args: Vec<String>,
+    /// Written to the standard input of the tool, keeping the token out of the command line
+    input: Option<String>,
+}
+
+impl Backend {
+    fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Backend::Keychain
+        } else if cfg!(windows) {
+            Backend::CredentialManager
+        } else {
+            Backend::SecretService
+        }
+    }
+
+    /// Gets the run printing the session token of an account
+    fn get(self, account: &str) -> Invocation {
+        match self {
+            Backend::Keychain => Invocation::new(
+                "security",
+                &["find-generic-password", "-s", SERVICE, "-a", account, "-w"],
+                None,
+            ),
+            Backend::CredentialManager => {
+                let script = format!(
+                    "{} $c = $vault.Retrieve('{}', '{}'); $c.RetrievePassword(); $c.Password",
+                    VAULT,
+                    SERVICE,
+                    account.replace('\'', "''")
+                );
+                Invocation::new("powershell", &["-NoProfile", "-Command", &script], None)
+            }
+            Backend::SecretService => Invocation::new(
+                "secret-tool",
+                &["lookup", "service", SERVICE, "account", account],
+                None,
+            ),
+        }
+    }
+
+    /// Gets the run storing the session token of an account, replacing the previous one
+    fn set(self, account: &str, token: &str) -> Invocation {
+        match self {
+            Backend::Keychain => {
+                // The interactive mode of security reads the command from the standard input,
+                // while `-w` without a value would prompt for the token on the terminal
+                let command = format!(
+                    "add-generic-password -U -s {} -a {} -w {}\n",
+                    quote(SERVICE),
+                    quote(account),
+                    quote(token)
+                );
+                Invocation::new("security", &["-i"], Some(command))
+            }
+            Backend::CredentialManager => {
+                let script = format!(
+                    "{} $vault.Add((New-Object Windows.Security.Credentials.PasswordCredential(\
+                     '{}', '{}', [Console]::In.ReadLine())))",
+                    VAULT,
+                    SERVICE,
+                    account.replace('\'', "''")
+                );
+                Invocation::new(
+                    "powershell",
+                    &["-NoProfile", "-Command", &script],
+                    Some(format!("{}\n", token)),
+                )
+            }
+            Backend::SecretService => {
+                let label = format!("{} session token ({})", SERVICE, account);
+                Invocation::new(
+                    "secret-tool",
+                    &[
+                        "store", "--label", &label, "service", SERVICE, "account", account,
+                    ],
+                    Some(token.to_string()),
+                )
+            }
+        }
+    }
+}
+
+impl Invocation {
+    fn new(program: &'static str, args: &[&str], input: Option<String>) -> Self {
+        Invocation {
+            program,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            input,
+        }
+    }
+
+    /// Runs the tool, and returns its standard output
+    fn run(&self) -> Result<String, String> {
+        let mut child = Command::new(self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                format!(
+                    "Could not run {} to access the keyring: {}",
+                    self.program, e
+                )
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            if let Some(input) = &self.input {
+                stdin
+                    .write_all(input.as_bytes())
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
+        let output = child.wait_with_output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(format!(
+                "{} failed: {}",
+                self.program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Quotes an argument of a command of `security -i`
+fn quote(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Gets a session token from the keyring of the system
+pub fn get(account: &str) -> Result<String, String> {
+    let token = Backend::current().get(account).run()?;
+
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(format!("No session token for {} in the keyring", account));
+    }
+    Ok(token.to_string())
+}
+
+/// Stores a session token in the keyring of the system, replacing the previous one
+pub fn set(account: &str, token: &str) -> Result<(), String> {
+    Backend::current().set(account, token).run()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "53616c7465645f5f";
+
+    #[test]
+    fn keychain() {
+        let get = Backend::Keychain.get("work");
+        assert_eq!(get.program, "security");
+        assert_eq!(
+            get.args,
+            [
+                "find-generic-password",
+                "-s",
+                "cargo-aoc",
+                "-a",
+                "work",
+                "-w"
+            ]
+        );
+        assert_eq!(get.input, None);
+
+        let set = Backend::Keychain.set("work", TOKEN);
+        assert_eq!(set.program, "security");
+        assert_eq!(set.args, ["-i"]);
+        assert_eq!(
+            set.input.as_deref(),
+            Some("add-generic-password -U -s \"cargo-aoc\" -a \"work\" -w \"53616c7465645f5f\"\n")
+        );
+    }
+
+    #[test]
+    fn credential_manager() {
+        let get = Backend::CredentialManager.get("o'brien");
+        assert_eq!(get.program, "powershell");
+        assert_eq!(get.args[..2], ["-NoProfile", "-Command"]);
+        assert!(get.args[2].contains("$vault.Retrieve('cargo-aoc', 'o''brien')"));
+
+        let set = Backend::CredentialManager.set("default", TOKEN);
+        assert!(set.args[2].contains("'cargo-aoc', 'default', [Console]::In.ReadLine()"));
+        assert!(!set.args.iter().any(|arg| arg.contains(TOKEN)));
+        assert_eq!(set.input.as_deref(), Some("53616c7465645f5f\n"));
+    }
+
+    #[test]
+    fn secret_service() {
+        let get = Backend::SecretService.get("default");
+        assert_eq!(get.program, "secret-tool");
+        assert_eq!(
+            get.args,
+            ["lookup", "service", "cargo-aoc", "account", "default"]
+        );
+
+        let set = Backend::SecretService.set("default", TOKEN);
+        assert_eq!(
+            set.args,
+            [
+                "store",
+                "--label",
+                "cargo-aoc session token (default)",
+                "service",
+                "cargo-aoc",
+                "account",
+                "default"
+            ]
+        );
+        assert_eq!(set.input.as_deref(), Some(TOKEN));
+    }
+
+    #[test]
+    fn quote_keychain_arguments() {
+        assert_eq!(quote("work"), "\"work\"");
+        assert_eq!(quote("a \"b\" c\\"), "\"a \\\"b\\\" c\\\\\"");
+    }
+}
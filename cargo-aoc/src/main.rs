@@ -1,17 +1,77 @@
+mod answers;
 mod app;
 mod args;
+mod badge;
+mod bench_backend;
+mod bench_env;
+mod bench_history;
+mod browser;
+mod build_profile;
+mod calendar;
+mod checksums;
+mod clipboard;
+mod color;
+mod compression;
+mod config;
 mod credentials;
+mod criterion;
 mod date;
+mod days;
+mod duration;
+mod doctor;
+mod encryption;
 mod project;
+mod puzzle;
+mod redact;
 mod errors;
+mod examples;
+mod features;
+mod fixtures;
+mod http;
+mod keyring;
+mod memory;
+mod notify;
+mod output;
+mod picker;
+mod progress;
+mod report;
+mod results;
+mod self_update;
+mod shared_cache;
+mod stack_size;
+mod stars;
+mod submissions;
+mod submit;
+mod target;
+mod time_format;
+mod titles;
+mod tui;
+mod workspace;
+mod worktree;
 
-use aoc_runner_internal::{Day, Part};
-use app::{execute_bench, execute_credentials, execute_default, execute_input};
+use aoc_runner_internal::{Day, DayParts, Part};
+use app::{
+    execute_answer, execute_badge, execute_bench, execute_calendar, execute_clean,
+    execute_credentials, execute_default, execute_doctor, execute_export, execute_init,
+    execute_input, execute_list, execute_new, execute_open, execute_puzzle, execute_report,
+    execute_self_update, execute_stars, execute_status, execute_submissions, execute_submit,
+    execute_test, execute_today, execute_tui, execute_verify, execute_watch, execute_whoami,
+};
+use bench_backend::BenchBackend;
+use build_profile::BuildProfile;
+use color::ColorChoice;
+use config::Config;
+use days::Days;
+use time_format::TimeUnit;
+use output::OutputFormat;
+use target::Target;
 
 use crate::args::args_without_aoc;
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use std::time::Duration;
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Default)]
 #[clap(
     name = "cargo-aoc",
     version = "0.3.8",
@@ -19,33 +79,190 @@ use clap::Parser;
     about = "Cargo helper for Advent of Code"
 )]
 pub struct Cli {
-    /// Specifies the day. Defaults to last implemented.
+    #[clap(flatten)]
+    run: Run,
+
+    #[clap(flatten)]
+    features: features::Features,
+
+    /// The member of the workspace holding the solutions (`-p` is the part).
+    #[clap(long, global = true)]
+    package: Option<String>,
+
+    /// When to color the output. `NO_COLOR` disables the colors in auto mode.
+    #[clap(long, value_enum, global = true, default_value_t)]
+    color: ColorChoice,
+
+    /// The unit of the printed durations.
+    #[clap(long, value_enum, global = true, default_value_t)]
+    time_format: TimeUnit,
+
+    /// The number of decimals of the printed durations.
+    #[clap(long, global = true)]
+    time_precision: Option<usize>,
+
+    /// Sends a desktop notification when a run takes longer than this duration (like 30s).
+    #[clap(long, global = true, value_parser = duration::parse)]
+    notify_after: Option<Duration>,
+
+    /// Runs the solutions on threads with this stack size (like 256MB), for deep recursions.
+    #[clap(long, global = true, value_parser = stack_size::parse)]
+    stack_size: Option<usize>,
+
+    /// Sends the requests to adventofcode.com through this proxy (like http://proxy:8080),
+    /// instead of the one of the HTTPS_PROXY and HTTP_PROXY environment variables.
+    #[clap(long, global = true)]
+    proxy: Option<String>,
+
+    /// Never uses the network: only the cached inputs are used, and submitting is disabled.
+    #[clap(long, global = true)]
+    offline: bool,
+
+    #[clap(subcommand)]
+    subcmd: Option<SubCommands>,
+}
+
+#[derive(Parser, Debug)]
+enum SubCommands {
+    Run(Run),
+    Answer(Answer),
+    Badge(Badge),
+    Bench(Bench),
+    Calendar(Calendar),
+    Clean(Clean),
+    Credentials(Credentials),
+    Doctor(Doctor),
+    Export(Export),
+    Init(Init),
+    Input(Input),
+    List(List),
+    New(New),
+    Open(Open),
+    Puzzle(Puzzle),
+    Report(Report),
+    SelfUpdate(SelfUpdate),
+    Stars(Stars),
+    Status(Status),
+    Submissions(Submissions),
+    Submit(Submit),
+    Test(Test),
+    Today(Today),
+    Tui(Tui),
+    Verify(Verify),
+    Watch(Watch),
+    Whoami(Whoami),
+}
+
+/// Runs the solutions of the last day (or a given day). This is the default command.
+#[derive(Parser, Debug, Clone, Default)]
+pub struct Run {
+    /// The year, day & part to run, like `2018 7 2`, `7 2` or the shorthand `d7p2`.
+    #[clap(value_name = "[YEAR] [DAY] [PART]", num_args = 0..=3)]
+    target: Vec<String>,
+
+    /// Specifies the day, a range (1..10) or a list (1,3,5). Defaults to last implemented.
     #[clap(short, long)]
-    day: Option<Day>,
+    day: Option<Days>,
 
     /// Specifies the part. Defaults to both parts.
     #[clap(short, long)]
     part: Option<Part>,
+    /// Only runs the alternative implementation with this name.
+    #[clap(long)]
+    alt: Option<String>,
     /// Use an alternate input file.
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = args::input_path)]
     input: Option<String>,
-    /// Add debug info for profiling tools.
-    #[clap(long)]
-    profile: bool,
+    /// Reads the input from the standard input, like `cat input.txt | cargo aoc -d 7 --stdin`.
+    #[clap(long, conflicts_with = "input")]
+    stdin: bool,
+    /// Runs the day against the examples of the puzzle instead, like `cargo aoc test`.
+    #[clap(long, conflicts_with_all = ["input", "stdin", "all", "all_years"])]
+    example: bool,
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
 
     /// Generate the boilerplate for the given day.
     #[clap(long, short)]
     generate: bool,
 
-    #[clap(subcommand)]
-    subcmd: Option<SubCommands>,
+    /// Runs every implemented day, and prints a summary with the total runtime.
+    #[clap(short, long)]
+    all: bool,
+
+    /// In a workspace with a crate per year, runs the crate holding the solutions of this year.
+    #[clap(long, conflicts_with = "all_years")]
+    year: Option<u32>,
+
+    /// In a workspace with a crate per year, runs the days of every year, with their totals.
+    #[clap(long)]
+    all_years: bool,
+
+    /// Number of days to run in parallel, when running several days.
+    #[clap(short, long)]
+    jobs: Option<usize>,
+
+    /// The format of the results.
+    #[clap(long, value_enum, default_value_t)]
+    output: OutputFormat,
+
+    /// Only prints `day part answer time` for each part, without the messages of cargo.
+    #[clap(short, long)]
+    quiet: bool,
+
+    /// Stops waiting for a part after this duration (like 30s or 2m), and reports a timeout.
+    #[clap(long, value_parser = duration::parse)]
+    timeout: Option<Duration>,
+
+    /// Runs each part this many times, and shows the mean ± standard deviation, the min & max
+    /// runtime, warning about the runs far from the others.
+    #[clap(long, default_value_t = 1)]
+    runs: usize,
+
+    /// Counts the allocations of each part, the bytes allocated and the peak of heap memory used,
+    /// with an allocator slowing the solutions down a little. The parts are run one at a time.
+    #[clap(long, conflicts_with = "jobs")]
+    memory: bool,
+
+    /// Copies the answer of the last part to the clipboard, to submit it on the website.
+    #[clap(long)]
+    clipboard: bool,
 }
 
+/// Stores the answer of a part, checked by the next runs of the part
 #[derive(Parser, Debug)]
-enum SubCommands {
-    Bench(Bench),
-    Credentials(Credentials),
-    Input(Input),
+pub struct Answer {
+    /// The answer to store. Defaults to the answer of the solution.
+    answer: Option<String>,
+
+    /// Specifies the day. Defaults to last implemented.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Specifies the part.
+    #[clap(short, long)]
+    part: Part,
+
+    /// Use an alternate input file.
+    #[clap(short, long, value_parser = args::input_path)]
+    input: Option<String>,
+}
+
+/// Runs every implemented day, and generates badges of the total runtime & solved parts
+#[derive(Parser, Debug)]
+pub struct Badge {
+    /// The directory where the SVG files are written.
+    #[clap(short, long, default_value = "badges")]
+    out: String,
+
+    /// Number of days to run in parallel.
+    #[clap(short, long)]
+    jobs: Option<usize>,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
 }
 
 /// Runs the benchmark for the last day (or a given day)
@@ -60,26 +277,152 @@ pub struct Bench {
     part: Option<Part>,
 
     /// Use an alternate input file.
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = args::input_path)]
     input: Option<String>,
 
+    /// Benchmarks every implemented day, and prints a summary with the total runtime.
+    #[clap(short, long, conflicts_with_all = ["day", "input", "compare_ref"])]
+    all: bool,
+
     /// Opens the benchmark information in the browser
     #[clap(short, long)]
     open: bool,
 
+    /// Only benchmarks the parts with alternative implementations, ranks them from the fastest,
+    /// and warns when their answers differ.
+    #[clap(long, conflicts_with_all = ["flamegraph", "compare_ref"])]
+    alts: bool,
+
     /// Also benchmark generator functions.
     #[clap(short, long)]
     generator: bool,
 
-    /// Add debug info for profiling tools.
+    /// Builds the benchmarks for the CPU of this machine (`-C target-cpu=native`).
     #[clap(long)]
-    profile: bool,
+    native_cpu: bool,
+
+    /// The library measuring the benchmarks: criterion, divan for quicker numbers, or
+    /// iai-callgrind for instruction counts.
+    #[clap(long, value_enum)]
+    backend: Option<BenchBackend>,
+
+    /// Profiles the benchmarks instead of measuring them, and writes a flamegraph of each
+    /// implementation in target/aoc/flamegraphs.
+    #[clap(long, conflicts_with = "open")]
+    flamegraph: bool,
+
+    /// Saves the results of criterion as a baseline with this name, to compare to it later.
+    #[clap(long, value_name = "NAME", conflicts_with = "flamegraph")]
+    save_baseline: Option<String>,
+
+    /// Compares the results of criterion to the baseline saved with this name.
+    #[clap(long, value_name = "NAME", conflicts_with_all = ["flamegraph", "save_baseline"])]
+    baseline: Option<String>,
+
+    /// Also benchmarks the parts at another git revision (like main), and prints the changes.
+    #[clap(
+        long,
+        value_name = "REF",
+        conflicts_with_all = ["flamegraph", "save_baseline", "baseline"]
+    )]
+    compare_ref: Option<String>,
+
+    /// Fails when an implementation is slower than its best recorded run by more than this
+    /// percentage (10 by default). Every run of criterion is recorded in benchmarks.json.
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        num_args = 0..=1,
+        default_missing_value = "10",
+        conflicts_with_all = ["flamegraph", "compare_ref"]
+    )]
+    check_regressions: Option<f64>,
+
+    /// Also runs the parts once with an allocator counting their allocations, the bytes
+    /// allocated and the peak of heap memory used.
+    #[clap(long, conflicts_with_all = ["flamegraph", "compare_ref"])]
+    memory: bool,
+
+    #[clap(flatten)]
+    criterion: criterion::Settings,
+
+    #[clap(flatten)]
+    environment: bench_env::Environment,
+
+    /// Writes the benchmarks to benches/aoc.rs instead of running them, and declares them in
+    /// Cargo.toml, for `cargo bench` to run them without cargo-aoc. Every day is written, unless
+    /// one is given.
+    #[clap(
+        long,
+        conflicts_with_all = [
+            "open", "flamegraph", "compare_ref", "save_baseline", "baseline",
+            "check_regressions", "memory", "alts"
+        ]
+    )]
+    write_harness: bool,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+
+    /// The format of the results.
+    #[clap(long, value_enum, default_value_t)]
+    output: OutputFormat,
 }
 
-/// Sets the session cookie
+/// Shows the calendar of December, and the time left before the next puzzle unlocks
+#[derive(Parser, Debug)]
+pub struct Calendar {
+    /// Specifies the year. Defaults to the year of the project.
+    #[clap(short, long)]
+    year: Option<i32>,
+
+    /// Counts down until the next puzzle unlocks, instead of only showing the time left
+    #[clap(short, long)]
+    wait: bool,
+}
+
+/// Removes the generated crates, and optionally the downloaded inputs.
+/// Defaults to the generated crates.
+#[derive(Parser, Debug)]
+pub struct Clean {
+    /// Removes the crate generated to run the solutions.
+    #[clap(long)]
+    build: bool,
+
+    /// Removes the crate generated for the benchmarks, and their results.
+    #[clap(long)]
+    bench: bool,
+
+    /// Removes the downloaded inputs.
+    #[clap(long)]
+    inputs: bool,
+
+    /// Removes everything.
+    #[clap(short, long)]
+    all: bool,
+}
+
+/// Sets (or checks) the session cookie
 #[derive(Parser, Debug)]
 pub struct Credentials {
+    #[clap(group = "token")]
     set: Option<String>,
+
+    /// Reads the session cookie from the cookies of Firefox, Chrome or Chromium, instead of
+    /// giving it.
+    #[clap(long, group = "token")]
+    from_browser: bool,
+
+    /// Stores the token in the keyring of the system (Keychain, Secret Service or
+    /// the Windows Credential Manager) instead of a plain text file.
+    #[clap(long, requires = "token")]
+    keyring: bool,
+
+    /// Checks that the stored token is still accepted by adventofcode.com, and tells which
+    /// account and profile it belongs to.
+    #[clap(long, conflicts_with = "token")]
+    check: bool,
 }
 
 /// Downloads the input for today (or a given day)
@@ -100,22 +443,372 @@ pub struct Input {
     /// Generate the boilerplate for the given day.
     #[clap(long, short)]
     generate: bool,
+
+    /// Waits for the puzzle to unlock before downloading it. Defaults to the next puzzle.
+    #[clap(short, long, conflicts_with = "all")]
+    wait: bool,
+
+    /// Number of inputs downloaded at once, with --all. Defaults to 4.
+    #[clap(short, long, requires = "all")]
+    jobs: Option<usize>,
+
+    /// Downloads the input again, even if it was already downloaded.
+    #[clap(short, long, conflicts_with = "wait")]
+    force: bool,
+
+    /// Checks that the downloaded inputs were not modified, instead of downloading them.
+    #[clap(long, conflicts_with_all = ["force", "wait", "generate"])]
+    check: bool,
+
+    /// Prints a shareable version of the input, with random numbers and identifiers.
+    #[clap(long, conflicts_with_all = ["all", "wait", "generate", "check"])]
+    redact: bool,
+}
+
+/// Checks your setup, and explains how to fix it
+#[derive(Parser, Debug)]
+pub struct Doctor {}
+
+/// Builds a standalone binary running the solutions, with the inputs embedded in it
+#[derive(Parser, Debug)]
+pub struct Export {
+    /// Specifies the days, like 7, 1..10 or 1,3,5. Defaults to all days.
+    #[clap(short, long)]
+    day: Option<Days>,
+
+    /// Where the binary is written. Defaults to aoc-{year}.
+    #[clap(short, long)]
+    output: Option<String>,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+}
+
+/// Creates a new solution crate for a given year
+#[derive(Parser, Debug)]
+pub struct Init {
+    /// The year of the puzzles.
+    year: u32,
+
+    /// Specifies the crate name. Defaults to advent-of-code-{year}.
+    #[clap(short, long)]
+    name: Option<String>,
+
+    /// Specifies the directory of the crate. Defaults to the crate name.
+    #[clap(long)]
+    path: Option<String>,
+}
+
+/// Lists the implemented days, parts and alternative implementations
+#[derive(Parser, Debug)]
+pub struct List {}
+
+/// Generates the boilerplate for a day, and downloads its input
+#[derive(Parser, Debug)]
+pub struct New {
+    /// The day to generate.
+    day: Day,
+
+    /// Specifies the year. Defaults to the year of the project.
+    #[clap(short, long)]
+    year: Option<i32>,
+}
+
+/// Opens the puzzle page in the browser
+#[derive(Parser, Debug)]
+pub struct Open {
+    /// Specifies the day. Defaults to today's puzzle, or the last implemented day.
+    #[clap(short, long)]
+    day: Option<Day>,
+}
+
+/// Prints the description of the puzzle in the terminal
+#[derive(Parser, Debug)]
+pub struct Puzzle {
+    /// Specifies the day. Defaults to today's puzzle, or the last implemented day.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Downloads the description again, even when it is cached.
+    #[clap(long)]
+    refresh: bool,
+}
+
+/// Runs every implemented day, and prints a Markdown report of the answers & timings
+#[derive(Parser, Debug)]
+pub struct Report {
+    /// Shows the answers, hidden by default.
+    #[clap(long)]
+    answers: bool,
+
+    /// Writes the report to a file instead of the standard output.
+    #[clap(short, long)]
+    out: Option<String>,
+
+    /// Replaces the section between `<!-- aoc-bench-start -->` and `<!-- aoc-bench-end -->`
+    /// of a file with the report. Defaults to README.md.
+    #[clap(long, num_args = 0..=1, default_missing_value = "README.md")]
+    readme: Option<String>,
+
+    /// Number of days to run in parallel.
+    #[clap(short, long)]
+    jobs: Option<usize>,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+
+    /// Writes an HTML page of the benchmarks recorded by `cargo aoc bench` instead, with their
+    /// history, without running anything. Defaults to target/aoc/report.html.
+    #[clap(long, conflicts_with_all = ["answers", "readme", "jobs", "profile"])]
+    html: bool,
+}
+
+/// Installs the last version of cargo-aoc released on crates.io
+#[derive(Parser, Debug)]
+pub struct SelfUpdate {
+    /// Only tells if a new version is available, without installing it.
+    #[clap(long)]
+    check: bool,
+
+    /// Installs the last version again, even if it's the installed one.
+    #[clap(long)]
+    force: bool,
 }
 
+/// Shows your stars, and when you got them, from your personal stats on adventofcode.com
+#[derive(Parser, Debug)]
+pub struct Stars {
+    /// Specifies the year. Defaults to the year of the project.
+    #[clap(short, long)]
+    year: Option<i32>,
+}
+
+/// Shows an overview of the project : the account, inputs, implemented and solved parts
+#[derive(Parser, Debug)]
+pub struct Status {}
+
+/// Shows the answers you submitted, and the verdicts of the server
+#[derive(Parser, Debug)]
+pub struct Submissions {
+    /// Specifies the day. Defaults to all days.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Specifies the year. Defaults to the year of the project.
+    #[clap(short, long)]
+    year: Option<i32>,
+}
+
+/// Runs a solution and submits its answer
+#[derive(Parser, Debug)]
+pub struct Submit {
+    /// Specifies the day. Defaults to last implemented.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Specifies the part to submit.
+    #[clap(short, long)]
+    part: Part,
+
+    /// Use an alternate input file.
+    #[clap(short, long, value_parser = args::input_path)]
+    input: Option<String>,
+
+    /// Submits the answer even if it was already rejected, or is out of the known range.
+    #[clap(long)]
+    force: bool,
+
+    /// Stops after a submission made too recently, instead of waiting to submit it again.
+    #[clap(long)]
+    no_retry: bool,
+
+    /// Shows what would be submitted, and with which account, without submitting it.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Submits without asking for a confirmation.
+    #[clap(short, long)]
+    yes: bool,
+}
+
+/// Runs the solutions against the examples of the puzzle
+#[derive(Parser, Debug)]
+pub struct Test {
+    /// Specifies the day. Defaults to last implemented.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Specifies the part. Defaults to both parts.
+    #[clap(short, long)]
+    part: Option<Part>,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+}
+
+/// Runs all the implemented parts of today's puzzle
+#[derive(Parser, Debug)]
+pub struct Today {
+    /// Use an alternate input file.
+    #[clap(short, long, value_parser = args::input_path)]
+    input: Option<String>,
+
+    /// Waits for the next puzzle to unlock, downloads its input and generates its boilerplate
+    #[clap(short, long)]
+    wait: bool,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+}
+
+/// Opens an interactive dashboard to run & benchmark the solutions, driven by the keyboard
+#[derive(Parser, Debug)]
+pub struct Tui {
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+}
+
+/// Runs the solutions, and compares them to the stored answers
+#[derive(Parser, Debug)]
+pub struct Verify {
+    /// Specifies the day. Defaults to all days.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Specifies the part. Defaults to both parts.
+    #[clap(short, long)]
+    part: Option<Part>,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+
+    /// The format of the results.
+    #[clap(long, value_enum, default_value_t)]
+    output: OutputFormat,
+
+    /// Stops waiting for a part after this duration (like 30s or 2m), and reports a timeout.
+    #[clap(long, value_parser = duration::parse)]
+    timeout: Option<Duration>,
+
+    /// Fails if the total runtime of the verified parts is over this duration (like 1s).
+    #[clap(long, value_parser = duration::parse)]
+    max_total: Option<Duration>,
+
+    /// Fails if a part takes more than this duration (like 100ms).
+    #[clap(long, value_parser = duration::parse)]
+    max_part: Option<Duration>,
+}
+
+/// Runs the solutions again every time the sources change
+#[derive(Parser, Debug)]
+pub struct Watch {
+    /// Specifies the day. Defaults to last implemented.
+    #[clap(short, long)]
+    day: Option<Day>,
+
+    /// Specifies the part. Defaults to both parts.
+    #[clap(short, long)]
+    part: Option<Part>,
+
+    /// Use an alternate input file.
+    #[clap(short, long, value_parser = args::input_path)]
+    input: Option<String>,
+
+    /// Add debug info for profiling tools, or build with the given cargo profile (like dev).
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    profile: Option<BuildProfile>,
+}
+
+/// Prints the Advent of Code account of the session token, to check which one is used
+#[derive(Parser, Debug)]
+pub struct Whoami {}
+
 fn main() {
-    let cli = Cli::parse_from(args_without_aoc());
+    let mut cli = Cli::parse_from(args_without_aoc());
+    color::init(cli.color);
+    time_format::init(cli.time_format, cli.time_precision);
+    features::init(cli.features);
+    notify::init(cli.notify_after);
+    stack_size::init(cli.stack_size);
+    http::init(cli.proxy, cli.offline);
+
+    let target_run = match &mut cli.subcmd {
+        None => Some(&mut cli.run),
+        Some(SubCommands::Run(run)) => Some(run),
+        _ => None,
+    };
+    if let Some(run) = target_run {
+        if let Err(err) = Target::parse(&run.target).and_then(|target| target.apply(run)) {
+            Cli::command().error(ErrorKind::ValueValidation, err).exit();
+        }
+    }
+
+    let run = match &cli.subcmd {
+        None => Some(&cli.run),
+        Some(SubCommands::Run(run)) => Some(run),
+        _ => None,
+    };
+    // Those don't need a project, and can create one in the current directory,
+    // while `--all-years` goes through every crate of the workspace
+    if !matches!(
+        cli.subcmd,
+        Some(
+            SubCommands::Init(_)
+                | SubCommands::Credentials(_)
+                | SubCommands::SelfUpdate(_)
+                | SubCommands::Whoami(_)
+        )
+    ) && !run.is_some_and(|run| run.all_years)
+    {
+        workspace::enter_project(cli.package.as_deref(), run.and_then(|run| run.year)).unwrap();
+    }
+
+    if !matches!(cli.subcmd, Some(SubCommands::SelfUpdate(_))) {
+        let project_year = DayParts::load().ok().map(|dp| dp.year as i32);
+        self_update::notices(project_year.or(Config::get().year));
+    }
 
     let Some(subcommand) = cli.subcmd else {
-        return execute_default(&cli).unwrap();
+        return execute_default(&cli.run).unwrap();
     };
 
     match subcommand {
+        SubCommands::Run(arg) => execute_default(&arg),
+        SubCommands::Answer(arg) => execute_answer(&arg),
+        SubCommands::Badge(arg) => execute_badge(&arg),
         SubCommands::Bench(arg) => execute_bench(&arg),
+        SubCommands::Calendar(arg) => execute_calendar(&arg),
+        SubCommands::Clean(arg) => execute_clean(&arg),
         SubCommands::Credentials(arg) => {
             execute_credentials(&arg);
             Ok(())
         }
+        SubCommands::Doctor(_) => execute_doctor(),
+        SubCommands::Export(arg) => execute_export(&arg),
+        SubCommands::Init(arg) => execute_init(&arg),
         SubCommands::Input(arg) => execute_input(&arg),
+        SubCommands::List(_) => execute_list(),
+        SubCommands::New(arg) => execute_new(&arg),
+        SubCommands::Open(arg) => execute_open(&arg),
+        SubCommands::Puzzle(arg) => execute_puzzle(&arg),
+        SubCommands::Report(arg) => execute_report(&arg),
+        SubCommands::SelfUpdate(arg) => execute_self_update(&arg),
+        SubCommands::Stars(arg) => execute_stars(&arg),
+        SubCommands::Status(_) => execute_status(),
+        SubCommands::Submissions(arg) => execute_submissions(&arg),
+        SubCommands::Submit(arg) => execute_submit(&arg),
+        SubCommands::Test(arg) => execute_test(&arg),
+        SubCommands::Today(arg) => execute_today(&arg),
+        SubCommands::Tui(arg) => execute_tui(&arg),
+        SubCommands::Verify(arg) => execute_verify(&arg),
+        SubCommands::Watch(arg) => execute_watch(&arg),
+        SubCommands::Whoami(_) => execute_whoami(),
     }
     .unwrap()
 }
@@ -0,0 +1,73 @@
+use serde::Serialize;
+use std::error;
+use std::sync::OnceLock;
+
+/// Whether the allocations of the solutions are counted, given with `--memory`
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+pub fn init(enabled: bool) {
+    let _ = ENABLED.set(enabled);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.get().copied().unwrap_or(false)
+}
+
+/// The allocations of an implementation, counted by the generated runner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Memory {
+    pub allocations: u64,
+    /// The bytes allocated, freed or not, a reallocation counting as a new allocation
+    pub allocated_bytes: u64,
+    /// The most heap memory allocated at once, above the heap used before the implementation
+    /// started. It's not the peak resident memory of the process, which includes its stack & code.
+    pub peak_heap_bytes: u64,
+}
+
+impl Memory {
+    /// Parses the fields added to the result line : `allocations allocated peak_heap`
+    pub fn parse(fields: &str) -> Result<Self, Box<dyn error::Error>> {
+        let fields: Vec<_> = fields.split('\t').collect();
+        let [allocations, allocated, peak_heap] = fields[..] else {
+            Err(format!("Invalid memory fields: {}", fields.join(" ")))?
+        };
+
+        Ok(Memory {
+            allocations: allocations.parse()?,
+            allocated_bytes: allocated.parse()?,
+            peak_heap_bytes: peak_heap.parse()?,
+        })
+    }
+}
+
+/// Formats a number of bytes with a binary unit, like `1.50 MiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.;
+    let mut unit = 0;
+    while value >= 1024. && unit < UNITS.len() - 1 {
+        value /= 1024.;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+/// Gets the `memory` module of the generated runner.
+///
+/// With `--memory`, it installs an allocator counting the allocations of the solutions, their
+/// counts being added to the result lines. Otherwise, the allocator of the system is left alone.
+pub fn template() -> String {
+    if enabled() {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/template/src/memory.rs.tpl"
+        ))
+        .to_string()
+    } else {
+        "#[allow(dead_code)]\nmod memory {\n    pub fn reset() {}\n\n    pub fn fields() -> String {\n        String::new()\n    }\n}".to_string()
+    }
+}
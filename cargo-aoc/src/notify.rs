@@ -0,0 +1,66 @@
+use crate::config::Config;
+use crate::time_format;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// The runtime after which a notification is sent, given on the command line
+static NOTIFY_AFTER: OnceLock<Option<Duration>> = OnceLock::new();
+
+pub fn init(after: Option<Duration>) {
+    let _ = NOTIFY_AFTER.set(after);
+}
+
+/// Gets the runtime after which a notification is sent, the one given on the command line first
+fn threshold() -> Option<Duration> {
+    NOTIFY_AFTER
+        .get()
+        .copied()
+        .flatten()
+        .or(Config::get().notify_after)
+}
+
+/// Sends a notification when a run took longer than the `--notify-after` duration
+pub fn finished(success: bool, elapsed: Duration) {
+    if threshold().is_some_and(|after| elapsed >= after) {
+        let outcome = if success { "finished" } else { "failed" };
+        send(
+            "cargo aoc",
+            &format!("The run {} after {}", outcome, time_format::format(elapsed)),
+        );
+    }
+}
+
+/// Sends a desktop notification, with the notification tool of the system.
+///
+/// Notifications are only a nicety : nothing happens when the tool is missing.
+pub fn send(title: &str, body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            body.replace('"', "'"),
+            title.replace('"', "'")
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(windows) {
+        let script = format!(
+            "[void][Reflection.Assembly]::LoadWithPartialName('System.Windows.Forms'); \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+             $n.ShowBalloonTip(10000, '{}', '{}', 'Info'); Start-Sleep 5; $n.Dispose()",
+            title.replace('\'', "''"),
+            body.replace('\'', "''")
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", &script]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args([title, body]);
+        command
+    };
+
+    let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).spawn();
+}
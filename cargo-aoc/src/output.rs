@@ -0,0 +1,434 @@
+use crate::criterion::Estimates;
+use crate::memory::Memory;
+use crate::results::{PartResult, Status};
+use crate::time_format::{self, TimeUnit};
+use aoc_runner_internal::DayPart;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::env;
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// The format of the results printed by the run, bench & verify commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable output
+    #[default]
+    Text,
+    /// A JSON array, with one object per implementation
+    Json,
+    /// A JUnit XML report, with one test case per implementation (verify only)
+    Junit,
+    /// A CSV table, with a header line and one line per implementation
+    Csv,
+    /// A Markdown table written to `$GITHUB_STEP_SUMMARY`, with annotations for the failures
+    Github,
+}
+
+impl OutputFormat {
+    /// Fails when the format is not one of those supported by a command
+    pub fn check(self, command: &str, supported: &[OutputFormat]) -> Result<(), String> {
+        if supported.contains(&self) {
+            return Ok(());
+        }
+        let name = self
+            .to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default();
+        Err(format!("The {} output is not available for {}", name, command))
+    }
+}
+
+/// A single implementation, as printed in machine readable outputs
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// The name of the alternative implementation, `null` for the default one
+    pub alt: Option<String>,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generator_ns: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runner_ns: Option<u128>,
+    /// The statistics of the total runtime, when the implementation was run several times
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunStats>,
+    /// The allocations of the implementation, counted with `--memory`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Memory>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunStats {
+    pub count: usize,
+    pub min_ns: u128,
+    pub mean_ns: u128,
+    pub std_dev_ns: u128,
+    pub max_ns: u128,
+    /// The number of runs far from the others
+    pub outliers: usize,
+}
+
+impl Record {
+    pub fn from_result(result: &PartResult) -> Self {
+        let ok = result.status == Status::Ok;
+
+        Record {
+            day: result.day.0,
+            part: result.part.0,
+            alt: result.name.clone(),
+            status: result.status.as_str(),
+            answer: Some(result.answer.clone()).filter(|_| ok),
+            expected: None,
+            error: Some(result.answer.clone()).filter(|_| !ok),
+            generator_ns: Some(result.generator.as_nanos()),
+            runner_ns: Some(result.runner.as_nanos()).filter(|_| ok),
+            runs: result.stats().map(|stats| RunStats {
+                count: result.samples.len(),
+                min_ns: stats.min.as_nanos(),
+                mean_ns: stats.mean.as_nanos(),
+                std_dev_ns: stats.std_dev.as_nanos(),
+                max_ns: stats.max.as_nanos(),
+                outliers: stats.outliers,
+            }),
+            memory: result.memory,
+        }
+    }
+
+    /// Gets the mean of the total runtime, the runtime itself when the implementation ran once
+    fn mean_ns(&self) -> Option<u128> {
+        match &self.runs {
+            Some(runs) => Some(runs.mean_ns),
+            None => Some(self.generator_ns? + self.runner_ns?),
+        }
+    }
+
+    /// Compares the answer to the expected one, the status becoming `passed` or `failed`
+    pub fn with_expected(mut self, expected: &str) -> Self {
+        if self.status == Status::Ok.as_str() {
+            self.status = if self.answer.as_deref() == Some(expected) {
+                "passed"
+            } else {
+                "failed"
+            };
+        }
+        self.expected = Some(expected.to_string());
+        self
+    }
+}
+
+/// The results of a benchmark, as printed in machine readable outputs.
+///
+/// Dashboards read these fields, so they are only ever added to, never renamed nor removed.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchRecord {
+    pub day: u8,
+    pub part: u8,
+    pub alt: Option<String>,
+    /// What is measured : the `runner`, or the `generator`
+    pub target: &'static str,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub std_dev_ns: f64,
+    /// The confidence level of the intervals below, between 0 and 1
+    pub confidence_level: f64,
+    pub mean_lower_ns: f64,
+    pub mean_upper_ns: f64,
+    pub median_lower_ns: f64,
+    pub median_upper_ns: f64,
+    pub std_dev_lower_ns: f64,
+    pub std_dev_upper_ns: f64,
+}
+
+impl BenchRecord {
+    pub fn new(dp: &DayPart, target: &'static str, estimates: &Estimates) -> Self {
+        BenchRecord {
+            day: dp.day.0,
+            part: dp.part.0,
+            alt: dp.name.clone(),
+            target,
+            mean_ns: estimates.mean.point_estimate,
+            median_ns: estimates.median.point_estimate,
+            std_dev_ns: estimates.std_dev.point_estimate,
+            confidence_level: estimates.mean.confidence_interval.confidence_level,
+            mean_lower_ns: estimates.mean.confidence_interval.lower_bound,
+            mean_upper_ns: estimates.mean.confidence_interval.upper_bound,
+            median_lower_ns: estimates.median.confidence_interval.lower_bound,
+            median_upper_ns: estimates.median.confidence_interval.upper_bound,
+            std_dev_lower_ns: estimates.std_dev.confidence_interval.lower_bound,
+            std_dev_upper_ns: estimates.std_dev.confidence_interval.upper_bound,
+        }
+    }
+}
+
+/// A record printed as a line of a CSV table
+pub trait CsvRow {
+    const HEADER: &'static [&'static str];
+
+    fn fields(&self) -> Vec<String>;
+}
+
+impl CsvRow for Record {
+    const HEADER: &'static [&'static str] = &[
+        "day",
+        "part",
+        "alt",
+        "status",
+        "answer",
+        "expected",
+        "error",
+        "generator_ns",
+        "runner_ns",
+        "mean_ns",
+        "std_dev_ns",
+        "runs",
+        "min_ns",
+        "max_ns",
+        "outliers",
+        "allocations",
+        "allocated_bytes",
+        "peak_heap_bytes",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        let runs = |field: fn(&RunStats) -> String| self.runs.as_ref().map(field);
+        vec![
+            self.day.to_string(),
+            self.part.to_string(),
+            self.alt.clone().unwrap_or_default(),
+            self.status.to_string(),
+            self.answer.clone().unwrap_or_default(),
+            self.expected.clone().unwrap_or_default(),
+            self.error.clone().unwrap_or_default(),
+            self.generator_ns.map(csv_time).unwrap_or_default(),
+            self.runner_ns.map(csv_time).unwrap_or_default(),
+            self.mean_ns().map(csv_time).unwrap_or_default(),
+            runs(|r| csv_time(r.std_dev_ns)).unwrap_or_default(),
+            runs(|r| r.count.to_string()).unwrap_or_default(),
+            runs(|r| csv_time(r.min_ns)).unwrap_or_default(),
+            runs(|r| csv_time(r.max_ns)).unwrap_or_default(),
+            runs(|r| r.outliers.to_string()).unwrap_or_default(),
+            self.memory.map(|m| m.allocations.to_string()).unwrap_or_default(),
+            self.memory.map(|m| m.allocated_bytes.to_string()).unwrap_or_default(),
+            self.memory.map(|m| m.peak_heap_bytes.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+impl CsvRow for BenchRecord {
+    const HEADER: &'static [&'static str] = &[
+        "day",
+        "part",
+        "alt",
+        "target",
+        "mean_ns",
+        "median_ns",
+        "std_dev_ns",
+        "confidence_level",
+        "mean_lower_ns",
+        "mean_upper_ns",
+        "median_lower_ns",
+        "median_upper_ns",
+        "std_dev_lower_ns",
+        "std_dev_upper_ns",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.day.to_string(),
+            self.part.to_string(),
+            self.alt.clone().unwrap_or_default(),
+            self.target.to_string(),
+            csv_time(self.mean_ns as u128),
+            csv_time(self.median_ns as u128),
+            csv_time(self.std_dev_ns as u128),
+            self.confidence_level.to_string(),
+            csv_time(self.mean_lower_ns as u128),
+            csv_time(self.mean_upper_ns as u128),
+            csv_time(self.median_lower_ns as u128),
+            csv_time(self.median_upper_ns as u128),
+            csv_time(self.std_dev_lower_ns as u128),
+            csv_time(self.std_dev_upper_ns as u128),
+        ]
+    }
+}
+
+/// Prints the records as a CSV table, quoting the fields when needed.
+///
+/// The timings are in nanoseconds, unless another unit is chosen with `--time-format`.
+pub fn print_csv<T: CsvRow>(records: &[T]) {
+    let unit = time_format::unit();
+    let header: Vec<_> = T::HEADER
+        .iter()
+        .map(|column| match column.strip_suffix("_ns") {
+            Some(name) if unit != TimeUnit::Auto => format!("{}_{}", name, unit.name()),
+            _ => column.to_string(),
+        })
+        .collect();
+    println!("{}", header.join(","));
+    for record in records {
+        let fields: Vec<_> = record.fields().iter().map(|f| csv_escape(f)).collect();
+        println!("{}", fields.join(","));
+    }
+}
+
+fn csv_time(ns: u128) -> String {
+    time_format::number(Duration::from_nanos(ns as u64))
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn print_json<T: Serialize>(records: &[T]) -> Result<(), serde_json::Error> {
+    println!("{}", serde_json::to_string_pretty(records)?);
+    Ok(())
+}
+
+/// Prints verified records as a JUnit report, in a single test suite
+pub fn print_junit(records: &[Record]) {
+    let failures = records.iter().filter(|r| r.status == "failed").count();
+    let errors = records
+        .iter()
+        .filter(|r| r.status != "failed" && r.status != "passed")
+        .count();
+    let time = |r: &Record| (r.generator_ns.unwrap_or(0) + r.runner_ns.unwrap_or(0)) as f64 / 1e9;
+
+    println!(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    println!(
+        r#"<testsuite name="cargo-aoc verify" tests="{}" failures="{}" errors="{}" time="{:.6}">"#,
+        records.len(),
+        failures,
+        errors,
+        records.iter().map(time).sum::<f64>()
+    );
+
+    for record in records {
+        let name = match &record.alt {
+            Some(alt) => format!("Part {} - {}", record.part, alt),
+            None => format!("Part {}", record.part),
+        };
+        print!(
+            r#"  <testcase classname="Day {}" name="{}" time="{:.6}""#,
+            record.day,
+            xml_escape(&name),
+            time(record)
+        );
+
+        match record.status {
+            "passed" => println!("/>"),
+            "failed" => {
+                let message = format!(
+                    "expected {} but got {}",
+                    record.expected.as_deref().unwrap_or_default(),
+                    record.answer.as_deref().unwrap_or_default()
+                );
+                println!(">");
+                println!(r#"    <failure message="{}"/>"#, xml_escape(&message));
+                println!("  </testcase>");
+            }
+            status => {
+                println!(">");
+                println!(
+                    r#"    <error type="{}" message="{}"/>"#,
+                    status,
+                    xml_escape(record.error.as_deref().unwrap_or_default())
+                );
+                println!("  </testcase>");
+            }
+        }
+    }
+
+    println!("</testsuite>");
+}
+
+/// Writes the records as a Markdown table in the summary of the GitHub Actions step,
+/// and prints an error annotation for each failure.
+///
+/// Outside of GitHub Actions, the table is printed on the standard output.
+pub fn print_github(title: &str, records: &[Record]) -> io::Result<()> {
+    let duration = |ns: Option<u128>| {
+        ns.map(|ns| time_format::format(Duration::from_nanos(ns as u64)))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let mut summary = format!("### {}\n\n", title);
+    summary += "| Day | Part | Name | Status | Generator | Runner |\n";
+    summary += "|----:|-----:|------|--------|----------:|-------:|\n";
+    for record in records {
+        let status = match record.status {
+            "ok" | "passed" => format!("✅ {}", record.status),
+            status => format!("❌ {}", status),
+        };
+        let _ = writeln!(
+            summary,
+            "| {} | {} | {} | {} | {} | {} |",
+            record.day,
+            record.part,
+            record.alt.as_deref().unwrap_or("(default)"),
+            status,
+            duration(record.generator_ns),
+            duration(record.runner_ns)
+        );
+    }
+
+    match env::var_os("GITHUB_STEP_SUMMARY") {
+        Some(path) => {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", summary)?;
+        }
+        None => println!("{}", summary),
+    }
+
+    for record in records {
+        let message = match record.status {
+            "ok" | "passed" => continue,
+            "failed" => format!(
+                "expected {} but got {}",
+                record.expected.as_deref().unwrap_or_default(),
+                record.answer.as_deref().unwrap_or_default()
+            ),
+            status => format!(
+                "{}: {}",
+                status,
+                record.error.as_deref().unwrap_or_default()
+            ),
+        };
+        let title = match &record.alt {
+            Some(alt) => format!("Day {} - Part {} - {}", record.day, record.part, alt),
+            None => format!("Day {} - Part {}", record.day, record.part),
+        };
+        // Annotations are single line, newlines must be escaped
+        println!(
+            "::error title={}::{}",
+            title.replace(',', "%2C").replace(':', "%3A"),
+            message
+                .replace('%', "%25")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A")
+        );
+    }
+
+    Ok(())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
@@ -0,0 +1,133 @@
+use aoc_runner_internal::{Day, DayParts, Part};
+use std::io::{self, BufRead, Write};
+
+/// The number of choices shown at once
+const SHOWN: usize = 15;
+
+/// A choice of the picker : a whole day, a part, or one of its alternative implementations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Choice {
+    pub day: Day,
+    pub part: Option<Part>,
+    pub alt: Option<String>,
+    label: String,
+}
+
+/// Lists the choices of the implemented days, the last day first
+pub fn choices(day_parts: &DayParts) -> Vec<Choice> {
+    let mut choices = Vec::new();
+    let mut dps: Vec<_> = day_parts.iter().collect();
+    dps.sort();
+
+    for dp in dps.iter().rev() {
+        if !choices.iter().any(|c: &Choice| c.day == dp.day) {
+            choices.push(Choice {
+                day: dp.day,
+                part: None,
+                alt: None,
+                label: format!("day {}", dp.day.0),
+            });
+        }
+        if !choices
+            .iter()
+            .any(|c| c.day == dp.day && c.part == Some(dp.part) && c.alt.is_none())
+        {
+            choices.push(Choice {
+                day: dp.day,
+                part: Some(dp.part),
+                alt: None,
+                label: format!("day {} part {}", dp.day.0, dp.part.0),
+            });
+        }
+        if let Some(name) = &dp.name {
+            choices.push(Choice {
+                day: dp.day,
+                part: Some(dp.part),
+                alt: Some(name.clone()),
+                label: format!("day {} part {} {}", dp.day.0, dp.part.0, name),
+            });
+        }
+    }
+
+    choices
+}
+
+/// Scores how well a label matches a query, lower being better.
+///
+/// The characters of the query must appear in order in the label, the score being the length
+/// of the matching span, so that `d7p2` gives `day 7 part 2` before `day 17 part 2`.
+fn score(label: &str, query: &str) -> Option<usize> {
+    let label: Vec<char> = label.chars().collect();
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let Some(first) = query.first() else {
+        return Some(0);
+    };
+
+    // Tries every start, keeping the shortest span
+    (0..label.len())
+        .filter(|&start| label[start] == *first)
+        .filter_map(|start| {
+            let mut end = start;
+            for c in &query[1..] {
+                end += 1 + label[end + 1..].iter().position(|l| l == c)?;
+            }
+            Some(end - start)
+        })
+        .min()
+}
+
+/// Filters the choices matching a query, the best matches first
+fn filter<'a>(choices: &'a [Choice], query: &str) -> Vec<&'a Choice> {
+    let mut matching: Vec<_> = choices
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| Some((score(&c.label, query)?, i, c)))
+        .collect();
+    matching.sort_by_key(|&(score, i, _)| (score, i));
+    matching.into_iter().map(|(_, _, c)| c).collect()
+}
+
+/// Asks which day, part or implementation to run, until one is picked or the user quits.
+///
+/// Typing text filters the choices, a number picks one, and an empty line picks the first.
+pub fn pick(choices: &[Choice]) -> io::Result<Option<Choice>> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut query = String::new();
+
+    loop {
+        let matching = filter(choices, &query);
+        println!();
+        for (i, choice) in matching.iter().take(SHOWN).enumerate() {
+            println!("{:>3}  {}", i + 1, choice.label);
+        }
+        if matching.len() > SHOWN {
+            println!("     ... {} more, type to filter", matching.len() - SHOWN);
+        } else if matching.is_empty() {
+            println!("     No match for {:?}", query);
+        }
+        print!("Pick a number, type to filter, or q to quit [1]: ");
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(None);
+        };
+        let line = line.trim();
+
+        match line {
+            "q" | "quit" => return Ok(None),
+            "" if !matching.is_empty() => return Ok(Some(matching[0].clone())),
+            "" => query.clear(),
+            _ => match line.parse::<usize>() {
+                Ok(n) if (1..=matching.len().min(SHOWN)).contains(&n) => {
+                    return Ok(Some(matching[n - 1].clone()))
+                }
+                _ => query = line.to_string(),
+            },
+        }
+    }
+}
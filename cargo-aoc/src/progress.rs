@@ -0,0 +1,73 @@
+use std::env;
+use std::fs;
+use std::sync::OnceLock;
+
+/// The first version of aoc-runner letting the solutions report their progress
+const PROGRESS_VERSION: (u64, u64, u64) = (0, 3, 1);
+
+/// Tells if the project uses a version of aoc-runner with `progress`, read from its Cargo.lock.
+///
+/// The generated crates then depend on this version, and show the reported progress. Otherwise,
+/// they keep to the older versions, the spinner only showing the elapsed time.
+pub fn supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+
+    *SUPPORTED.get_or_init(|| locked_version().is_some_and(|v| v >= PROGRESS_VERSION))
+}
+
+/// Gets the version of aoc-runner in the Cargo.lock of the project, or of its workspace
+fn locked_version() -> Option<(u64, u64, u64)> {
+    let dir = env::current_dir().ok()?;
+    let lock = dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.exists())?;
+    let lock: toml::Table = fs::read_to_string(lock).ok()?.parse().ok()?;
+
+    lock.get("package")?
+        .as_array()?
+        .iter()
+        .filter(|package| package.get("name").and_then(|n| n.as_str()) == Some("aoc-runner"))
+        .filter_map(|package| parse_version(package.get("version")?.as_str()?))
+        .max()
+}
+
+/// Parses a version like `0.3.1`, ignoring its pre-release or build metadata
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.split(['-', '+']).next()?;
+    let mut numbers = version.split('.').map(|n| n.parse().ok());
+    Some((numbers.next()??, numbers.next()??, numbers.next()??))
+}
+
+/// Gets the version of aoc-runner the generated crates depend on
+pub fn dependency() -> &'static str {
+    if supported() {
+        "0.3.1"
+    } else {
+        "0.3.0"
+    }
+}
+
+/// Gets the `progress` module of the generated runner, reading the progress reported by the
+/// solutions when aoc-runner has it
+pub fn template() -> String {
+    if supported() {
+        "mod progress {\n    pub use aoc_runner::current_progress as current;\n    pub use aoc_runner::reset_progress as reset;\n}".to_string()
+    } else {
+        "#[allow(dead_code)]\nmod progress {\n    pub fn current() -> Option<(u64, u64)> {\n        None\n    }\n\n    pub fn reset() {}\n}".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_versions() {
+        assert_eq!(parse_version("0.3.1"), Some((0, 3, 1)));
+        assert_eq!(parse_version("1.12.0-beta.2"), Some((1, 12, 0)));
+        assert_eq!(parse_version("0.3.0+local"), Some((0, 3, 0)));
+        assert_eq!(parse_version("0.3"), None);
+        assert_eq!(parse_version("0.x.1"), None);
+    }
+}
@@ -3,12 +3,19 @@ use std::error;
 use std::fs;
 use std::process;
 use crate::errors::CouldNotLoadDayParts;
+use crate::features;
+use crate::workspace;
+
+/// Where aoc-runner-derive writes the list of the implemented day parts
+const DAY_PARTS_PATH: &str = "target/aoc/completed.json";
 
 #[derive(Clone, Debug)]
 pub struct ProjectManager {
     pub name: String,
     pub slug: String,
     pub lib_path: Option<String>,
+    /// Hides the messages of cargo, except for the errors
+    pub quiet: bool,
 }
 
 impl ProjectManager {
@@ -36,13 +43,21 @@ impl ProjectManager {
             name: crate_name,
             slug: crate_slug,
             lib_path,
+            quiet: false,
         })
     }
 
     pub fn build_project(&self) -> Result<DayParts, Box<dyn error::Error>> {
-        let args = vec!["check", "--color=always"];
+        let mut args = vec!["check", "--color=always"];
+        if self.quiet {
+            args.push("--quiet");
+        }
 
-        let status = process::Command::new("cargo").args(&args).spawn()?.wait()?;
+        let status = process::Command::new("cargo")
+            .args(&args)
+            .args(features::cargo_args())
+            .spawn()?
+            .wait()?;
 
         if !status.success() {
             return Err(format!(
@@ -52,6 +67,27 @@ impl ProjectManager {
             .into());
         }
 
+        self.take_workspace_day_parts()?;
+
         DayParts::load().map_err(|err| CouldNotLoadDayParts(err).into())
     }
+
+    /// Moves the day parts found by aoc-runner-derive to the target directory of the crate.
+    ///
+    /// Cargo compiles the members of a workspace from its root, where the list of the day parts
+    /// is then written. It is moved to the crate, keeping the one of each member apart.
+    fn take_workspace_day_parts(&self) -> Result<(), Box<dyn error::Error>> {
+        let Some(root) = workspace::root() else {
+            return Ok(());
+        };
+        let written = root.join(DAY_PARTS_PATH);
+        if fs::canonicalize(root)? == fs::canonicalize(".")? || !written.exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all("target/aoc")?;
+        fs::rename(written, DAY_PARTS_PATH)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,248 @@
+use crate::calendar;
+use crate::color;
+use crate::compression;
+use crate::config::Config;
+use crate::date::AOCDate;
+use crate::http::{self, Endpoint};
+use reqwest::StatusCode;
+use std::error;
+use std::fs;
+use std::path::PathBuf;
+
+/// The width the paragraphs are wrapped to
+const WIDTH: usize = 80;
+
+/// Gets the path of the cached description of a puzzle : `{input_dir}/{year}/puzzles/day{day}.html`,
+/// with a `.zst` extension when it's compressed
+pub fn path(date: AOCDate) -> PathBuf {
+    let path = format!(
+        "{}/{}/puzzles/day{}.html",
+        Config::get().input_dir(),
+        date.year,
+        date.day
+    );
+    if compression::enabled() {
+        PathBuf::from(format!("{}.{}", path, compression::EXTENSION))
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+/// Reads the cached description of a puzzle
+pub fn cached(date: AOCDate) -> Option<String> {
+    let content = fs::read(path(date)).ok()?;
+    let content = if compression::enabled() {
+        compression::decompress(&content).ok()?
+    } else {
+        content
+    };
+    String::from_utf8(content).ok()
+}
+
+/// Gets the description of a puzzle, from the cache when it holds both parts.
+///
+/// The part 2 is only shown to logged in users who solved the part 1,
+/// so the page is downloaded again until it appears, unless in offline mode.
+pub fn load(
+    date: AOCDate,
+    token: Option<&str>,
+    refresh: bool,
+) -> Result<String, Box<dyn error::Error>> {
+    load_from(&Endpoint::PAGE, date, token, refresh)
+}
+
+/// Gets the description of a puzzle like [`load`], downloading it through the given endpoint
+pub fn load_from(
+    endpoint: &Endpoint,
+    date: AOCDate,
+    token: Option<&str>,
+    refresh: bool,
+) -> Result<String, Box<dyn error::Error>> {
+    let path = path(date);
+    let cached = cached(date);
+    if let Some(cached) = &cached {
+        if !refresh && (articles(cached).len() == 2 || http::offline()) {
+            return Ok(cached.clone());
+        }
+    }
+
+    let response = http::get(endpoint, &date.puzzle_url(), token)?;
+
+    match response.status {
+        StatusCode::OK => {
+            let description = articles(&response.body).join("\n");
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            if compression::enabled() {
+                fs::write(&path, compression::compress(description.as_bytes())?)?;
+            } else {
+                fs::write(&path, &description)?;
+            }
+            Ok(description)
+        }
+        // Keeps the cached part 1 when the page can't be downloaded again
+        _ if cached.is_some() => Ok(cached.unwrap_or_default()),
+        StatusCode::NOT_FOUND => Err(calendar::not_unlocked(date.year, date.day).into()),
+        sc => Err(format!("Could not download the puzzle. Status: {}", sc).into()),
+    }
+}
+
+/// Extracts the `<article>` elements of the page, one per part
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + end + "</article>".len();
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    articles
+}
+
+/// Renders the description of a puzzle for the terminal.
+///
+/// Only the few elements used by the descriptions are handled : the title, paragraphs,
+/// lists, code blocks, and the highlighted text, shown in bold (or yellow for the stars).
+pub fn render(html: &str) -> String {
+    let mut out = String::new();
+    let mut spans: Vec<(String, Option<&'static str>)> = Vec::new();
+    let mut styles: Vec<&'static str> = Vec::new();
+    let mut pre = false;
+
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        spans.push((decode(&rest[..start]), styles.last().copied()));
+        let tag = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                flush(&mut out, &mut spans, pre);
+                styles.push(color::BOLD);
+            }
+            ("pre", false) => {
+                flush(&mut out, &mut spans, pre);
+                pre = true;
+            }
+            ("pre", true) => {
+                flush(&mut out, &mut spans, pre);
+                pre = false;
+            }
+            ("li", false) => {
+                flush(&mut out, &mut spans, pre);
+                spans.push(("- ".to_string(), None));
+            }
+            ("h2", true) => {
+                styles.pop();
+                flush(&mut out, &mut spans, pre);
+            }
+            ("p" | "li" | "ul" | "article", _) => flush(&mut out, &mut spans, pre),
+            ("em", false) if tag.contains("star") => styles.push(color::YELLOW),
+            ("em", false) => styles.push(color::BOLD),
+            ("code", false) if !pre => styles.push(color::GREEN),
+            ("em", true) => {
+                styles.pop();
+            }
+            ("code", true) if !pre => {
+                styles.pop();
+            }
+            // Links, spans with easter eggs... only keep their text
+            _ => {}
+        }
+    }
+    spans.push((decode(rest), None));
+    flush(&mut out, &mut spans, pre);
+
+    out
+}
+
+/// Writes a block of text, wrapping the paragraphs, and indenting the code blocks
+fn flush(out: &mut String, spans: &mut Vec<(String, Option<&'static str>)>, pre: bool) {
+    if spans.iter().all(|(text, _)| text.trim().is_empty()) {
+        spans.clear();
+        return;
+    }
+
+    if pre {
+        let mut block = String::from("    ");
+        for (text, style) in spans.drain(..) {
+            for (i, line) in text.split('\n').enumerate() {
+                if i > 0 {
+                    block.push_str("\n    ");
+                }
+                block.push_str(&paint(style, line));
+            }
+        }
+        out.push_str(block.trim_end());
+    } else {
+        let mut width = 0;
+        let mut space = false;
+        for (text, style) in spans.drain(..) {
+            space |= text.starts_with(char::is_whitespace);
+            for (i, word) in text.split_whitespace().enumerate() {
+                space |= i > 0;
+                let len = word.chars().count();
+                if space && width > 0 && width + 1 + len > WIDTH {
+                    out.push('\n');
+                    width = 0;
+                } else if space && width > 0 {
+                    out.push(' ');
+                    width += 1;
+                }
+                space = false;
+                out.push_str(&paint(style, word));
+                width += len;
+            }
+            space |= text.ends_with(char::is_whitespace);
+        }
+    }
+    out.push_str("\n\n");
+}
+
+fn paint(style: Option<&str>, text: &str) -> String {
+    match style {
+        Some(style) if !text.is_empty() => color::paint(style, text),
+        _ => text.to_string(),
+    }
+}
+
+/// Gets the text of some HTML, without its tags
+pub fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode(&text)
+}
+
+/// Decodes the HTML entities used by the descriptions
+fn decode(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,121 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Makes a shareable version of an input, with the same structure : every number is replaced by a
+/// random one of the same length, and every identifier by a random one of the same length and
+/// case, the same identifier always giving the same one.
+///
+/// Single letters are kept, since they are usually symbols of the puzzle, like directions.
+pub fn redact(input: &str) -> String {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    Redactor {
+        rng: Rng(seed | 1),
+        identifiers: HashMap::new(),
+        used: HashSet::new(),
+    }
+    .redact(input)
+}
+
+struct Redactor {
+    rng: Rng,
+    identifiers: HashMap<String, String>,
+    used: HashSet<String>,
+}
+
+impl Redactor {
+    fn redact(&mut self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut chars = input.chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c.is_ascii_digit() || c.is_ascii_alphabetic() {
+                let digits = c.is_ascii_digit();
+                let mut token = String::new();
+                while let Some(&c) = chars.peek() {
+                    let same_kind = if digits {
+                        c.is_ascii_digit()
+                    } else {
+                        c.is_ascii_alphabetic()
+                    };
+                    if !same_kind {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                if digits {
+                    output.push_str(&self.number(&token));
+                } else {
+                    output.push_str(&self.identifier(&token));
+                }
+            } else {
+                output.push(c);
+                chars.next();
+            }
+        }
+
+        output
+    }
+
+    /// Replaces a number by a random one of the same length, without a leading zero
+    /// unless it had one
+    fn number(&mut self, number: &str) -> String {
+        number
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let lowest = if i == 0 && c != '0' && number.len() > 1 {
+                    1
+                } else {
+                    0
+                };
+                char::from(b'0' + lowest + self.rng.below(10 - lowest as u64) as u8)
+            })
+            .collect()
+    }
+
+    /// Replaces an identifier by a random one, always the same one for a given identifier
+    fn identifier(&mut self, identifier: &str) -> String {
+        if identifier.len() < 2 {
+            return identifier.to_string();
+        }
+        if let Some(replacement) = self.identifiers.get(identifier) {
+            return replacement.clone();
+        }
+
+        let replacement = loop {
+            let replacement: String = identifier
+                .chars()
+                .map(|c| {
+                    let letter = char::from(b'a' + self.rng.below(26) as u8);
+                    if c.is_ascii_uppercase() {
+                        letter.to_ascii_uppercase()
+                    } else {
+                        letter
+                    }
+                })
+                .collect();
+            // Two identifiers must stay different
+            if replacement != identifier && self.used.insert(replacement.clone()) {
+                break replacement;
+            }
+        };
+        self.identifiers
+            .insert(identifier.to_string(), replacement.clone());
+        replacement
+    }
+}
+
+/// A xorshift generator, good enough to hide an input
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
@@ -0,0 +1,241 @@
+use crate::bench_history::BenchRun;
+use crate::criterion::{self, Estimates};
+use crate::results::{self, PartResult, Status};
+use crate::time_format;
+use crate::titles;
+use aoc_runner_internal::Day;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// Builds a Markdown table of the results, with the total runtime.
+///
+/// The first part of each day shows the title of its puzzle, when it is known.
+/// The benchmark column shows the mean measured by criterion during the last `cargo aoc bench`
+/// of the day, when there is one.
+pub fn markdown(results: &[PartResult], year: u32, show_answers: bool) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "| Day | Part | Name | Answer | Generator | Runner | Benchmark |"
+    );
+    let _ = writeln!(out, "|----:|-----:|------|--------|----------:|-------:|----------:|");
+
+    let mut last_day = None;
+    for result in results {
+        let day = match titles::name(year, result.day) {
+            Some(name) if last_day != Some(result.day) => {
+                format!("{}: {}", result.day.0, name.replace('|', "\\|"))
+            }
+            _ => result.day.0.to_string(),
+        };
+        last_day = Some(result.day);
+        let answer = match result.status {
+            Status::Ok if show_answers => format!("`{}`", result.answer.replace('\n', " ")),
+            Status::Ok => "hidden".to_string(),
+            _ => "FAILED".to_string(),
+        };
+        let benchmark = bench_time(result, year)
+            .map(time_format::format)
+            .unwrap_or_else(|| "-".to_string());
+
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            day,
+            result.part.0,
+            result.name.as_deref().unwrap_or("(default)"),
+            answer.replace('|', "\\|"),
+            time_format::format(result.generator),
+            time_format::format(result.runner),
+            benchmark
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "\n**Total: {}**",
+        time_format::format(results::total_time(results))
+    );
+    out
+}
+
+/// The number of runs shown by the sparkline of an implementation
+const HISTORY_LENGTH: usize = 30;
+
+/// Builds a single HTML page of the benchmarks recorded by `cargo aoc bench`, with a line per
+/// implementation : its last & best means, and a sparkline of its history.
+///
+/// The totals add up the fastest implementation of each part.
+pub fn html(runs: &[BenchRun], year: u32) -> String {
+    // The runs of each implementation, the oldest first, the runners before the generators
+    let mut implementations: BTreeMap<_, Vec<&BenchRun>> = BTreeMap::new();
+    for run in runs {
+        let key = (run.day, run.part, run.target != "runner", run.alt.clone());
+        implementations.entry(key).or_default().push(run);
+    }
+
+    let mut rows = String::new();
+    let mut fastest_last: BTreeMap<(u8, u8), f64> = BTreeMap::new();
+    let mut fastest_best: BTreeMap<(u8, u8), f64> = BTreeMap::new();
+    let mut last_day = None;
+    for ((day, part, generator, alt), runs) in &implementations {
+        let last = runs[runs.len() - 1];
+        let best = runs.iter().map(|r| r.mean_ns).fold(f64::INFINITY, f64::min);
+        if !generator {
+            let last_total = fastest_last.entry((*day, *part)).or_insert(f64::INFINITY);
+            *last_total = last_total.min(last.mean_ns);
+            let best_total = fastest_best.entry((*day, *part)).or_insert(f64::INFINITY);
+            *best_total = best_total.min(best);
+        }
+
+        let day_cell = match titles::name(year, Day(*day)) {
+            Some(name) if last_day != Some(*day) => format!("{}: {}", day, escape(&name)),
+            _ => day.to_string(),
+        };
+        last_day = Some(*day);
+        let mut name = escape(alt.as_deref().unwrap_or("(default)"));
+        if *generator {
+            name += " (generator)";
+        }
+        let history: Vec<f64> = runs
+            .iter()
+            .rev()
+            .take(HISTORY_LENGTH)
+            .rev()
+            .map(|r| r.mean_ns)
+            .collect();
+
+        let _ = writeln!(
+            rows,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{} <small>± {}</small></td><td>{}</td>\
+             <td>{}</td><td>{}</td></tr>",
+            day_cell,
+            part,
+            name,
+            format_ns(last.mean_ns),
+            format_ns(last.std_dev_ns),
+            format_ns(best),
+            runs.len(),
+            sparkline(&history)
+        );
+    }
+
+    let last_total: f64 = fastest_last.values().sum();
+    let best_total: f64 = fastest_best.values().sum();
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Advent of Code {year} - Benchmarks</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }}
+td:nth-child(n+4) {{ text-align: right; }}
+tfoot td {{ font-weight: bold; }}
+small {{ color: #888; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code {year} - Benchmarks</h1>
+<table>
+<thead><tr>
+<th>Day</th><th>Part</th><th>Implementation</th><th>Last</th><th>Best</th><th>Runs</th><th>History</th>
+</tr></thead>
+<tbody>
+{rows}</tbody>
+<tfoot><tr>
+<td colspan="3">Total, with the fastest implementation of each part</td>
+<td>{last}</td><td>{best}</td><td></td><td></td>
+</tr></tfoot>
+</table>
+</body>
+</html>
+"#,
+        year = year,
+        rows = rows,
+        last = format_ns(last_total),
+        best = format_ns(best_total)
+    )
+}
+
+/// Draws the means of the last runs as an inline SVG line, the fastest at the bottom
+fn sparkline(values: &[f64]) -> String {
+    const WIDTH: f64 = 120.;
+    const HEIGHT: f64 = 24.;
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let step = WIDTH / (values.len().max(2) - 1) as f64;
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let y = if max > min {
+                HEIGHT - 2. - (value - min) / (max - min) * (HEIGHT - 4.)
+            } else {
+                HEIGHT / 2.
+            };
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{}\" height=\"{}\"><polyline points=\"{}\" fill=\"none\" \
+         stroke=\"#36c\" stroke-width=\"1.5\"/></svg>",
+        WIDTH,
+        HEIGHT,
+        points.join(" ")
+    )
+}
+
+fn format_ns(ns: f64) -> String {
+    time_format::format(Duration::from_nanos(ns as u64))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub const SECTION_START: &str = "<!-- aoc-bench-start -->";
+pub const SECTION_END: &str = "<!-- aoc-bench-end -->";
+
+/// Replaces the content between the section markers of a document, keeping everything else
+pub fn replace_section(document: &str, section: &str) -> Result<String, String> {
+    let start = document
+        .find(SECTION_START)
+        .ok_or_else(|| format!("Missing {} marker", SECTION_START))?
+        + SECTION_START.len();
+    let end = document[start..]
+        .find(SECTION_END)
+        .ok_or_else(|| format!("Missing {} marker after {}", SECTION_END, SECTION_START))?
+        + start;
+
+    Ok(format!(
+        "{}\n{}\n{}",
+        &document[..start],
+        section.trim_end(),
+        &document[end..]
+    ))
+}
+
+/// Gets the mean runtime of the last benchmark of an implementation
+fn bench_time(result: &PartResult, year: u32) -> Option<Duration> {
+    let groups = titles::bench_groups(year, result.day);
+    let id = criterion::bench_id(result.part, result.name.as_deref());
+
+    Estimates::load_first(
+        Path::new("target/aoc/aoc-autobench/target/criterion"),
+        &groups,
+        &id,
+    )
+    .ok()
+    .map(|e| Duration::from_nanos(e.mean.point_estimate as u64))
+}
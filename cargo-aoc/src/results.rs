@@ -0,0 +1,521 @@
+use crate::color;
+use crate::memory::{self, Memory};
+use crate::time_format;
+use crate::titles;
+use aoc_runner_internal::{Day, Part};
+use std::error;
+use std::time::Duration;
+
+/// The outcome of a single implementation, as reported by the generated runner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    GenError,
+    RunError,
+    Panic,
+    /// The implementation didn't finish before the timeout
+    Timeout,
+}
+
+/// Starts the lines of the runner output holding a result, the other lines being printed by the
+/// solutions themselves. Solutions don't print this control character by accident.
+pub const MARKER: char = '\x1e';
+
+/// The result of a single implementation, parsed from the output of the generated runner.
+///
+/// Each result line follows the [`MARKER`], and is tab separated :
+/// `day part name status generator runner answer`,
+/// with the timings in nanoseconds, and the answer (or error) escaped on a single line.
+/// With `--memory`, the line of a successful implementation ends with
+/// `allocations allocated peak_heap`.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: Part,
+    pub name: Option<String>,
+    pub status: Status,
+    pub generator: Duration,
+    pub runner: Duration,
+    /// The answer, or the error message if the implementation failed
+    pub answer: String,
+    /// The total runtime of each run, when the implementation was run several times
+    pub samples: Vec<Duration>,
+    /// The allocations of the implementation, counted with `--memory`
+    pub memory: Option<Memory>,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::GenError => "gen_error",
+            Status::RunError => "run_error",
+            Status::Panic => "panic",
+            Status::Timeout => "timeout",
+        }
+    }
+}
+
+impl PartResult {
+    pub fn parse(line: &str) -> Result<Self, Box<dyn error::Error>> {
+        let fields: Vec<_> = line.splitn(7, '\t').collect();
+        let [day, part, name, status, generator, runner, answer] = fields[..] else {
+            Err(format!("Invalid runner output: {}", line))?
+        };
+
+        let status = [
+            Status::Ok,
+            Status::GenError,
+            Status::RunError,
+            Status::Panic,
+            Status::Timeout,
+        ]
+        .into_iter()
+        .find(|s| s.as_str() == status)
+        .ok_or_else(|| format!("Invalid runner status: {}", status))?;
+
+        // The answer is escaped, the memory fields are the only ones that can follow it
+        let (answer, memory) = match answer.split_once('\t') {
+            Some((answer, memory)) => (answer, Some(Memory::parse(memory)?)),
+            None => (answer, None),
+        };
+
+        Ok(PartResult {
+            day: Day(day.parse()?),
+            part: Part(part.parse()?),
+            name: Some(name.to_string()).filter(|n| !n.is_empty()),
+            status,
+            generator: Duration::from_nanos(generator.parse()?),
+            runner: Duration::from_nanos(runner.parse()?),
+            answer: unescape(answer),
+            samples: Vec::new(),
+            memory,
+        })
+    }
+
+    /// Parses the result lines of the generated runner, without their marker
+    pub fn parse_all(output: &str) -> Result<Vec<Self>, Box<dyn error::Error>> {
+        output
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(PartResult::parse)
+            .collect()
+    }
+
+    /// Merges the results of the implementations that were run several times.
+    ///
+    /// The timings become the mean of the runs, and the first failure is kept if any.
+    pub fn merge_runs(results: Vec<Self>) -> Vec<Self> {
+        let mut merged: Vec<Self> = Vec::new();
+
+        for result in results {
+            let sample = result.generator + result.runner;
+            let existing = merged.iter_mut().find(|r| {
+                (r.day, r.part, &r.name) == (result.day, result.part, &result.name)
+            });
+
+            match existing {
+                Some(existing) => {
+                    let n = existing.samples.len() as u32;
+                    existing.generator = (existing.generator * n + result.generator) / (n + 1);
+                    existing.runner = (existing.runner * n + result.runner) / (n + 1);
+                    existing.samples.push(sample);
+                    if existing.status == Status::Ok && result.status != Status::Ok {
+                        existing.status = result.status;
+                        existing.answer = result.answer;
+                    }
+                }
+                None => merged.push(PartResult {
+                    samples: vec![sample],
+                    ..result
+                }),
+            }
+        }
+
+        merged
+    }
+
+    /// Gets the statistics of the runtime, when the implementation was run several times
+    pub fn stats(&self) -> Option<Stats> {
+        if self.samples.len() < 2 {
+            return None;
+        }
+        let nanos: Vec<f64> = self.samples.iter().map(|s| s.as_nanos() as f64).collect();
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.);
+
+        let scores = modified_z_scores(&nanos);
+        let outliers = scores
+            .iter()
+            .filter(|s| s.abs() > OUTLIER_THRESHOLD)
+            .count();
+        Some(Stats {
+            mean: Duration::from_nanos(mean as u64),
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            min: *self.samples.iter().min()?,
+            max: *self.samples.iter().max()?,
+            outliers,
+            slow_first_run: scores[0] > OUTLIER_THRESHOLD,
+        })
+    }
+
+    /// Identifies the implementation for the generated runner, which skips the ones already run
+    /// when it is started again after a timeout
+    pub fn key(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.day.0,
+            self.part.0,
+            self.name.as_deref().unwrap_or_default()
+        )
+    }
+
+    pub fn display(&self) -> String {
+        match &self.name {
+            Some(n) => format!("Day {} - Part {} - {}", self.day.0, self.part.0, n),
+            None => format!("Day {} - Part {}", self.day.0, self.part.0),
+        }
+    }
+}
+
+/// The statistics of the runtime of an implementation run several times with `--runs`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    /// The standard deviation of the runs
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    /// The number of runs far from the others, usually slowed down by another program
+    pub outliers: usize,
+    /// Whether the first run was an outlier, slower than the others, like with cold caches
+    pub slow_first_run: bool,
+}
+
+/// The modified Z-score above which a run is an outlier, as high as the one of hyperfine since
+/// runtimes often have a long tail
+const OUTLIER_THRESHOLD: f64 = 14.;
+
+/// Gets how far each sample is from the median, in median absolute deviations (scaled to be
+/// comparable to standard deviations), 0 for every sample when most of them are equal
+fn modified_z_scores(samples: &[f64]) -> Vec<f64> {
+    fn median(values: &[f64]) -> f64 {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2.
+        } else {
+            sorted[middle]
+        }
+    }
+
+    let median_sample = median(samples);
+    let deviations: Vec<f64> = samples.iter().map(|x| (x - median_sample).abs()).collect();
+    let mad = median(&deviations);
+    if mad == 0. {
+        return vec![0.; samples.len()];
+    }
+    samples
+        .iter()
+        .map(|x| 0.6745 * (x - median_sample) / mad)
+        .collect()
+}
+
+/// Reverts `str::escape_debug`, used by the generated runner to keep answers on a single line
+pub fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('0') => out.push('\0'),
+            Some('u') => {
+                let code: String = chars
+                    .by_ref()
+                    .skip_while(|&c| c == '{')
+                    .take_while(|&c| c != '}')
+                    .collect();
+                if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    out.push(c);
+                }
+            }
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+/// Prints a compact table of results, one line per implementation,
+/// the parts of each day following the title of its puzzle when it is known
+pub fn print_table(results: &[PartResult], year: u32) {
+    let names: Vec<_> = results
+        .iter()
+        .map(|r| r.name.as_deref().unwrap_or("(default)"))
+        .collect();
+    let name_width = names.iter().map(|n| n.len()).max().unwrap_or(0).max(4);
+    let answer_width = results
+        .iter()
+        .map(|r| r.answer.len())
+        .max()
+        .unwrap_or(0)
+        .clamp(6, 40);
+
+    let stats = results.iter().any(|r| r.stats().is_some());
+    let memory = results.iter().any(|r| r.memory.is_some());
+
+    print!(
+        "{:>3} {:>4}  {:<name_width$}  {:<answer_width$}  {:>12}  {:>12}",
+        "Day", "Part", "Name", "Answer", "Generator", "Runner"
+    );
+    if stats {
+        print!("  {:>25}  {:>12}  {:>12}", "Mean ± σ", "Min", "Max");
+    }
+    if memory {
+        print!(
+            "  {:>12}  {:>12}  {:>12}",
+            "Allocations", "Allocated", "Peak heap"
+        );
+    }
+    println!();
+
+    let mut last_day = None;
+    for (result, name) in results.iter().zip(names) {
+        if last_day != Some(result.day) {
+            last_day = Some(result.day);
+            if let Some(title) = titles::get(year, result.day) {
+                println!("{}", color::paint(color::BOLD, &format!("--- {} ---", title)));
+            }
+        }
+
+        // Padded before being colored, the escape codes would count in the width
+        let answer = match result.status {
+            Status::Ok => color::paint(
+                color::BOLD,
+                &format!("{:<answer_width$}", result.answer.replace('\n', " ")),
+            ),
+            _ => color::paint(
+                color::RED,
+                &format!("{:<answer_width$}", format!("FAILED: {}", result.answer)),
+            ),
+        };
+
+        print!(
+            "{:>3} {:>4}  {:<name_width$}  {}  {}  {}",
+            result.day.0,
+            result.part.0,
+            name,
+            answer,
+            color::time(result.generator, 12),
+            color::time(result.runner, 12),
+        );
+        if let Some(stats) = result.stats() {
+            print!(
+                "  {} ± {:<10}  {}  {}",
+                color::time(stats.mean, 12),
+                time_format::format(stats.std_dev),
+                color::time(stats.min, 12),
+                color::time(stats.max, 12)
+            );
+        }
+        if memory {
+            print!("  {}", memory_columns(result.memory));
+        }
+        println!();
+    }
+
+    print_outliers(results);
+}
+
+/// Warns about the implementations whose runs vary too much for their mean to be trusted, like
+/// hyperfine does
+fn print_outliers(results: &[PartResult]) {
+    for result in results {
+        let Some(stats) = result.stats() else {
+            continue;
+        };
+        let warning = if stats.slow_first_run {
+            format!(
+                "{} : the first run was much slower than the others, \
+                 caches were probably cold. Consider ignoring it with more --runs.",
+                result.display()
+            )
+        } else if stats.outliers > 0 {
+            format!(
+                "{} : {} of the {} runs are statistical outliers. \
+                 Consider running again on a quieter system.",
+                result.display(),
+                stats.outliers,
+                result.samples.len()
+            )
+        } else {
+            continue;
+        };
+        let warning = format!("Warning: {}", warning);
+        println!("{}", color::paint(color::YELLOW, &warning));
+    }
+}
+
+/// Prints the allocations of each implementation, counted with `--memory`
+pub fn print_memory(results: &[PartResult]) {
+    let names: Vec<_> = results
+        .iter()
+        .map(|r| r.name.as_deref().unwrap_or("(default)"))
+        .collect();
+    let name_width = names.iter().map(|n| n.len()).max().unwrap_or(0).max(4);
+
+    println!(
+        "{:>3} {:>4}  {:<name_width$}  {:>12}  {:>12}  {:>12}",
+        "Day", "Part", "Name", "Allocations", "Allocated", "Peak heap"
+    );
+    for (result, name) in results.iter().zip(names) {
+        println!(
+            "{:>3} {:>4}  {:<name_width$}  {}",
+            result.day.0,
+            result.part.0,
+            name,
+            memory_columns(result.memory)
+        );
+    }
+}
+
+/// Formats the allocations of an implementation in three columns, empty when they weren't counted
+fn memory_columns(memory: Option<Memory>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "{:>12}  {:>12}  {:>12}",
+            memory.allocations,
+            memory::format_bytes(memory.allocated_bytes),
+            memory::format_bytes(memory.peak_heap_bytes)
+        ),
+        None => format!("{:>12}  {:>12}  {:>12}", "-", "-", "-"),
+    }
+}
+
+/// Prints a single line per implementation : `day part answer time`, with the time in nanoseconds
+/// (or in the unit chosen with `--time-format`).
+///
+/// The part is followed by the name of alternative implementations (`1:for_loop`), and the errors
+/// are printed on the standard error, with `FAILED` as the answer.
+pub fn print_quiet(results: &[PartResult]) {
+    for result in results {
+        let part = match &result.name {
+            Some(n) => format!("{}:{}", result.part.0, n),
+            None => result.part.0.to_string(),
+        };
+        let answer = match result.status {
+            Status::Ok => result.answer.replace('\n', " "),
+            _ => {
+                eprintln!("{} : FAILED, {}", result.display(), result.answer);
+                "FAILED".to_string()
+            }
+        };
+
+        println!(
+            "{} {} {} {}",
+            result.day.0,
+            part,
+            answer,
+            time_format::number(result.generator + result.runner)
+        );
+    }
+}
+
+/// Gets the total runtime, generators included, counting a single implementation per part :
+/// the default one when it exists
+pub fn total_time(results: &[PartResult]) -> Duration {
+    let mut parts: Vec<_> = results.iter().map(|r| (r.day, r.part)).collect();
+    parts.sort();
+    parts.dedup();
+
+    parts
+        .into_iter()
+        .filter_map(|(day, part)| {
+            let mut implementations = results.iter().filter(|r| r.day == day && r.part == part);
+            implementations
+                .clone()
+                .find(|r| r.name.is_none())
+                .or_else(|| implementations.next())
+        })
+        .map(|r| r.generator + r.runner)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_result_line() {
+        let result = PartResult::parse("3\t2\tfast\tok\t1500\t25000\t1234").unwrap();
+        assert_eq!((result.day, result.part), (Day(3), Part(2)));
+        assert_eq!(result.name.as_deref(), Some("fast"));
+        assert_eq!(result.status, Status::Ok);
+        assert_eq!(result.generator, Duration::from_nanos(1500));
+        assert_eq!(result.runner, Duration::from_nanos(25000));
+        assert_eq!(result.answer, "1234");
+        assert!(result.memory.is_none());
+        assert_eq!(result.key(), "3/2/fast");
+    }
+
+    #[test]
+    fn parse_default_implementation() {
+        let result = PartResult::parse("12\t1\t\tpanic\t0\t0\tindex out of bounds").unwrap();
+        assert_eq!(result.name, None);
+        assert_eq!(result.status, Status::Panic);
+        assert_eq!(result.answer, "index out of bounds");
+        assert_eq!(result.key(), "12/1/");
+    }
+
+    #[test]
+    fn parse_escaped_answer() {
+        let result = PartResult::parse("1\t1\t\tok\t0\t10\t#..#\\n.##.\\t\\u{1b}").unwrap();
+        assert_eq!(result.answer, "#..#\n.##.\t\u{1b}");
+    }
+
+    #[test]
+    fn parse_memory_fields() {
+        let result = PartResult::parse("1\t2\t\tok\t10\t20\t42\t7\t2048\t1024").unwrap();
+        assert_eq!(result.answer, "42");
+        let memory = result.memory.unwrap();
+        assert_eq!(memory.allocations, 7);
+        assert_eq!(memory.allocated_bytes, 2048);
+        assert_eq!(memory.peak_heap_bytes, 1024);
+    }
+
+    #[test]
+    fn parse_invalid_lines() {
+        assert!(PartResult::parse("").is_err());
+        assert!(PartResult::parse("1\t1\t\tok\t0\t0").is_err());
+        assert!(PartResult::parse("1\t1\t\tdone\t0\t0\t42").is_err());
+        assert!(PartResult::parse("x\t1\t\tok\t0\t0\t42").is_err());
+        assert!(PartResult::parse("1\t1\t\tok\t0\t0\t42\t7").is_err());
+    }
+
+    #[test]
+    fn unescape_debug_strings() {
+        for answer in [
+            "42",
+            "a\\b",
+            "\"quoted\"",
+            "line\nline",
+            "tab\there",
+            "\u{1b}[0m",
+            "é",
+        ] {
+            let escaped = answer.escape_debug().to_string();
+            assert_eq!(unescape(&escaped), answer);
+        }
+        assert_eq!(unescape("trailing\\"), "trailing\\");
+    }
+}
@@ -0,0 +1,104 @@
+use crate::config::Config;
+use crate::date::AOCDate;
+use crate::http::{self, Endpoint};
+use serde::Deserialize;
+use std::env;
+use std::error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// The version of the running cargo-aoc
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How often the notices are shown
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: Crate,
+}
+
+#[derive(Debug, Deserialize)]
+struct Crate {
+    max_stable_version: String,
+}
+
+/// Gets the last stable version of cargo-aoc released on crates.io
+pub fn latest_version() -> Result<String, Box<dyn error::Error>> {
+    let response = http::get(
+        &Endpoint::CRATES_IO,
+        "https://crates.io/api/v1/crates/cargo-aoc",
+        None,
+    )?;
+    if !response.status.is_success() {
+        return Err(format!("crates.io answered {}", response.status).into());
+    }
+
+    let response: CrateResponse = serde_json::from_str(&response.body)?;
+    Ok(response.krate.max_stable_version)
+}
+
+/// Tells if a version is newer than another, comparing their numbers one by one
+pub fn is_newer(version: &str, than: &str) -> bool {
+    let numbers = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .map_while(|n| n.parse().ok())
+            .collect()
+    };
+    numbers(version) > numbers(than)
+}
+
+/// Tells about a new version of cargo-aoc, and about a new event when the project is for
+/// a previous year, at most once a day.
+///
+/// Those are only hints : they are printed on stderr, and every error is ignored.
+/// `check_updates = false` in aoc.toml, or the `CARGO_AOC_NO_UPDATE_CHECK` environment variable,
+/// turns them off.
+pub fn notices(project_year: Option<i32>) {
+    if env::var_os("CARGO_AOC_NO_UPDATE_CHECK").is_some()
+        || !Config::get().check_updates
+        || http::offline()
+    {
+        return;
+    }
+
+    let Some(path) = last_check_path() else {
+        return;
+    };
+    let checked = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed < CHECK_INTERVAL);
+    if checked {
+        return;
+    }
+    // Written before checking, an unreachable crates.io would slow down every command otherwise
+    let _ = fs::write(&path, CURRENT_VERSION);
+
+    if let (Some(year), Some(event)) = (project_year, AOCDate::current_puzzle()) {
+        if year < event.year {
+            eprintln!(
+                "Advent of Code {} has started! Run `cargo aoc init {}` to create a crate for it",
+                event.year, event.year
+            );
+        }
+    }
+
+    if let Ok(latest) = latest_version() {
+        if is_newer(&latest, CURRENT_VERSION) {
+            eprintln!(
+                "cargo-aoc {} is available (installed: {}), run `cargo aoc self-update` to install it",
+                latest, CURRENT_VERSION
+            );
+        }
+    }
+}
+
+/// Gets the file telling when the last version check was made, by its modification time
+fn last_check_path() -> Option<PathBuf> {
+    Some(http::cache_dir()?.join("last-update-check"))
+}
@@ -0,0 +1,65 @@
+//! Shares the inputs with the cache of another tool, with `shared_cache = "aocd"` in aoc.toml,
+//! so that an input is downloaded once whichever tool asks for it first.
+//!
+//! `advent-of-code-data` stores the inputs like `~/.config/aocd/{user id}/2023_01_input.txt`,
+//! the id of the user of each token being in `token2id.json`. `aoc-cli` has no cache of its own.
+
+use crate::config::{Config, SharedCache};
+use crate::date::AOCDate;
+use directories::BaseDirs;
+use std::collections::BTreeMap;
+use std::env;
+use std::error;
+use std::fs;
+use std::path::PathBuf;
+
+/// The environment variable moving the directory of `advent-of-code-data`
+const AOCD_DIR_ENV: &str = "AOCD_DIR";
+
+/// Reads an input from the shared cache, if it is there
+pub fn read(date: AOCDate, token: &str) -> Option<String> {
+    let input = fs::read_to_string(path(date, token)?).ok()?;
+    (!input.trim().is_empty()).then_some(input)
+}
+
+/// Writes a downloaded input to the shared cache.
+///
+/// Nothing is written if the user of the token is unknown to the other tool, since its
+/// directories are named after the user id and not the token.
+pub fn write(date: AOCDate, token: &str, input: &str) -> Result<(), Box<dyn error::Error>> {
+    let Some(path) = path(date, token) else {
+        return Ok(());
+    };
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, input).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+/// Gets the path of an input in the shared cache
+fn path(date: AOCDate, token: &str) -> Option<PathBuf> {
+    match Config::get().shared_cache? {
+        SharedCache::Aocd => {
+            let dir = aocd_dir()?;
+            let ids: BTreeMap<String, String> =
+                serde_json::from_str(&fs::read_to_string(dir.join("token2id.json")).ok()?).ok()?;
+            let id = ids.get(token)?;
+            Some(
+                dir.join(id)
+                    .join(format!("{}_{:02}_input.txt", date.year, date.day)),
+            )
+        }
+    }
+}
+
+/// Gets the directory of `advent-of-code-data`: `$AOCD_DIR`, or `~/.config/aocd`
+fn aocd_dir() -> Option<PathBuf> {
+    match env::var_os(AOCD_DIR_ENV) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(BaseDirs::new()?.home_dir().join(".config/aocd")),
+    }
+}
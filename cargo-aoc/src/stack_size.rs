@@ -0,0 +1,56 @@
+use crate::config::Config;
+use serde::{de, Deserialize, Deserializer};
+use std::sync::OnceLock;
+
+/// The stack size of the threads running the solutions, given on the command line
+static STACK_SIZE: OnceLock<Option<usize>> = OnceLock::new();
+
+pub fn init(size: Option<usize>) {
+    let _ = STACK_SIZE.set(size);
+}
+
+/// Gets the stack size of the threads running the solutions, the one given on the command line
+/// first. Without any, the default stack of the main thread is used.
+pub fn get() -> Option<usize> {
+    STACK_SIZE
+        .get()
+        .copied()
+        .flatten()
+        .or(Config::get().stack_size)
+}
+
+/// Parses a size, like `256MB`, `512k` or `1G`. A number without unit is a number of bytes.
+pub fn parse(s: &str) -> Result<usize, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: usize = value.parse().map_err(|_| format!("Invalid size: {}", s))?;
+    let multiplier = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        unit => return Err(format!("Invalid size unit: {}", unit)),
+    };
+
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Size too large: {}", s))
+}
+
+/// Deserializes a size of aoc.toml, written like on the command line
+pub fn deserialize_option<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<usize>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse(&s).map(Some).map_err(de::Error::custom)
+}
+
+/// Gets the code of the stack size, for the generated runner
+pub fn template() -> String {
+    match get() {
+        Some(size) => format!("Some({})", size),
+        None => "None".to_string(),
+    }
+}
@@ -0,0 +1,151 @@
+use crate::calendar;
+use crate::color;
+use crate::http::{self, Endpoint};
+use crate::puzzle;
+use chrono::{Duration, Local};
+use reqwest::StatusCode;
+use std::error;
+use std::fmt::Write;
+
+/// When a part was solved, counted from the unlock of the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solved {
+    After(Duration),
+    /// The personal stats only give the time of the first 24 hours
+    Later,
+}
+
+/// The parts solved for a day
+#[derive(Debug, Clone, Copy)]
+pub struct DayStats {
+    pub day: u32,
+    pub parts: [Option<Solved>; 2],
+}
+
+/// Downloads the personal stats of a year, from `https://adventofcode.com/{year}/leaderboard/self`.
+///
+/// The page is cached for 15 minutes, as asked by the Advent of Code.
+pub fn fetch(year: i32, token: &str) -> Result<Vec<DayStats>, Box<dyn error::Error>> {
+    let response = http::get(
+        &Endpoint::STATS,
+        &http::url(&format!("/{}/leaderboard/self", year)),
+        Some(token),
+    )?;
+
+    match response.status {
+        StatusCode::OK => {
+            let page = response.body;
+            if !page.contains("[Log Out]") {
+                Err("The session token is not valid anymore, \
+                     set it again using `cargo aoc credentials {token}`")?
+            }
+            Ok(parse(&page))
+        }
+        StatusCode::NOT_FOUND => Err(format!("Advent of Code {} has not started yet", year).into()),
+        sc => Err(format!("Could not download your stats. Status: {}", sc).into()),
+    }
+}
+
+/// Parses the table of the personal stats, a line per day having a star :
+///
+/// ```text
+/// Day       Time   Rank  Score       Time   Rank  Score
+///   7   00:12:34   1234      0   01:02:03   2345      0
+///   6       >24h  45678      0          -      -      -
+/// ```
+fn parse(page: &str) -> Vec<DayStats> {
+    let table = match (page.find("<pre"), page.find("</pre>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => return Vec::new(),
+    };
+
+    let mut days = Vec::new();
+    for line in puzzle::text(table).lines() {
+        let mut tokens = line.split_whitespace();
+        let Some(Ok(day)) = tokens.next().map(str::parse::<u32>) else {
+            continue;
+        };
+
+        // The ranks and scores are skipped, only the times are kept
+        let mut times = tokens.filter_map(|token| match token {
+            ">24h" => Some(Some(Solved::Later)),
+            "-" => Some(None),
+            token if token.contains(':') => Some(parse_time(token).map(Solved::After)),
+            _ => None,
+        });
+        // The columns of an unsolved part 2 are dashes
+        let part1 = times.next().flatten();
+        let part2 = times.next().flatten();
+
+        if part1.is_some() {
+            days.push(DayStats {
+                day,
+                parts: [part1, part2],
+            });
+        }
+    }
+    days.sort_by_key(|d| d.day);
+    days
+}
+
+/// Parses a time like `01:02:03`
+fn parse_time(time: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    for field in time.split(':') {
+        seconds = seconds * 60 + field.parse::<i64>().ok()?;
+    }
+    Some(Duration::seconds(seconds))
+}
+
+/// Renders the star map of a year, with when each part was solved, in the local timezone
+pub fn render(year: i32, days: &[DayStats]) -> String {
+    let stars: usize = days.iter().map(|d| d.parts.iter().flatten().count()).sum();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "AOC {} - {} stars\n", year, stars);
+    let _ = writeln!(out, "{:>3}  {:<5} {:<28} Part 2", "Day", "", "Part 1");
+
+    for day in 1..=calendar::days(year) {
+        let stats = days.iter().find(|d| d.day == day);
+        let parts = stats.map(|s| s.parts).unwrap_or_default();
+        let count = parts.iter().flatten().count();
+
+        // Padded before being colored, the escape codes would count in the width
+        let map = format!("{:<5}", "*".repeat(count));
+        let map = if count > 0 {
+            color::paint(color::YELLOW, &map)
+        } else {
+            map
+        };
+
+        let _ = writeln!(
+            out,
+            "{:>3}  {} {:<28} {}",
+            day,
+            map,
+            solved_at(year, day, parts[0]),
+            solved_at(year, day, parts[1])
+        );
+    }
+
+    out
+}
+
+/// Formats when a part was solved, like `Dec 07 06:12:34 (00:12:34)`
+fn solved_at(year: i32, day: u32, solved: Option<Solved>) -> String {
+    match solved {
+        None => String::new(),
+        Some(Solved::Later) => "more than 24h later".to_string(),
+        Some(Solved::After(elapsed)) => {
+            let at = (calendar::unlock_time(year, day) + elapsed).with_timezone(&Local);
+            let seconds = elapsed.num_seconds();
+            format!(
+                "{} ({:02}:{:02}:{:02})",
+                at.format("%b %d %H:%M:%S"),
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )
+        }
+    }
+}
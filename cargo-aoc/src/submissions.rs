@@ -0,0 +1,223 @@
+use crate::color;
+use crate::config::Config;
+use crate::submit::Verdict;
+use aoc_runner_internal::{Day, Part};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::error;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+/// A submitted answer, with the verdict of the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// When the answer was submitted, in the RFC 3339 format
+    pub time: String,
+    /// `correct`, `incorrect`, `too-recent`, `already-solved` or `unknown`
+    pub verdict: String,
+    /// The hint given by the server, like `Your answer is too high.`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+impl Submission {
+    /// Records a submission made now
+    pub fn new(day: Day, part: Part, answer: &str, verdict: &Verdict) -> Self {
+        Submission {
+            day: day.0,
+            part: part.0,
+            answer: answer.to_string(),
+            time: Utc::now().to_rfc3339(),
+            verdict: verdict.name().to_string(),
+            message: verdict.message().to_string(),
+        }
+    }
+}
+
+/// Gets the path of the submissions of a year : `{account_dir}/{year}/submissions.json`
+fn path(year: i32) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/{}/submissions.json",
+        Config::get().account_dir(),
+        year
+    ))
+}
+
+/// Loads the submissions of a year, the oldest first, none if there is no log yet
+pub fn load(year: i32) -> Result<Vec<Submission>, Box<dyn error::Error>> {
+    let path = path(year);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(serde_json::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?)
+}
+
+/// Appends a submission to the log of a year
+pub fn append(year: i32, submission: Submission) -> Result<(), Box<dyn error::Error>> {
+    let mut submissions = load(year)?;
+    submissions.push(submission);
+
+    let path = path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&submissions)?)?;
+    Ok(())
+}
+
+/// Checks an answer against the previous submissions of a part, before sending it : the answers
+/// already rejected, and the range left by the ones that were too high or too low
+pub fn check(submissions: &[Submission], day: Day, part: Part, answer: &str) -> Result<(), String> {
+    let rejected = submissions
+        .iter()
+        .filter(|s| s.day == day.0 && s.part == part.0 && s.verdict == "incorrect");
+
+    let value = answer.parse::<i128>().ok();
+    for s in rejected {
+        if s.answer == answer {
+            return Err(format!("{} was already rejected on {}", answer, s.time));
+        }
+
+        let Some((value, bound)) = value.zip(s.answer.parse::<i128>().ok()) else {
+            continue;
+        };
+        if s.message.contains("too high") && value >= bound {
+            return Err(format!("{} is too high, {} already was", value, bound));
+        }
+        if s.message.contains("too low") && value <= bound {
+            return Err(format!("{} is too low, {} already was", value, bound));
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders the submissions, grouped by day, in the local timezone
+pub fn render(submissions: &[Submission], day: Option<Day>) -> String {
+    let mut days: Vec<u8> = submissions.iter().map(|s| s.day).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut out = String::new();
+    for d in days
+        .into_iter()
+        .filter(|&d| day.map_or(true, |day| day.0 == d))
+    {
+        let _ = writeln!(out, "{}", color::paint(color::BOLD, &format!("Day {}", d)));
+
+        let mut attempts: Vec<_> = submissions.iter().filter(|s| s.day == d).collect();
+        attempts.sort_by_key(|s| s.part);
+        for s in attempts {
+            let time = DateTime::parse_from_rfc3339(&s.time)
+                .map(|t| {
+                    t.with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_else(|_| s.time.clone());
+
+            // Padded before being colored, the escape codes would count in the width
+            let verdict = format!("{:<14}", s.verdict);
+            let verdict = match s.verdict.as_str() {
+                "correct" => color::paint(color::GREEN, &verdict),
+                "incorrect" => color::paint(color::RED, &verdict),
+                _ => verdict,
+            };
+
+            let line = format!(
+                "  Part {}  {}  {:<16} {} {}",
+                s.part, time, s.answer, verdict, s.message
+            );
+            let _ = writeln!(out, "{}", line.trim_end());
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected(part: u8, answer: &str, message: &str) -> Submission {
+        Submission {
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            time: "2023-12-01T05:10:00+00:00".to_string(),
+            verdict: "incorrect".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn no_submissions() {
+        assert!(check(&[], Day(1), Part(1), "42").is_ok());
+    }
+
+    #[test]
+    fn already_rejected() {
+        let submissions = [rejected(1, "abc", "")];
+        assert!(check(&submissions, Day(1), Part(1), "abc").is_err());
+        assert!(check(&submissions, Day(1), Part(1), "abd").is_ok());
+    }
+
+    #[test]
+    fn within_the_bounds() {
+        let submissions = [
+            rejected(1, "100", "Your answer is too high."),
+            rejected(1, "10", "Your answer is too low."),
+        ];
+        for answer in ["11", "50", "99"] {
+            assert!(check(&submissions, Day(1), Part(1), answer).is_ok());
+        }
+    }
+
+    #[test]
+    fn outside_the_bounds() {
+        let submissions = [
+            rejected(1, "100", "Your answer is too high."),
+            rejected(1, "10", "Your answer is too low."),
+        ];
+        for answer in ["100", "101", "10", "9", "-5"] {
+            assert!(check(&submissions, Day(1), Part(1), answer).is_err());
+        }
+    }
+
+    #[test]
+    fn bounds_of_big_numbers() {
+        // Above the answers of most puzzles, which fit in 64 bits
+        let bound = "18446744073709551616";
+        let submissions = [rejected(1, bound, "Your answer is too low.")];
+        assert!(check(&submissions, Day(1), Part(1), "18446744073709551615").is_err());
+        assert!(check(&submissions, Day(1), Part(1), "18446744073709551617").is_ok());
+    }
+
+    #[test]
+    fn bounds_of_other_parts_and_answers() {
+        let submissions = [
+            rejected(2, "100", "Your answer is too high."),
+            rejected(1, "100", ""),
+            Submission {
+                verdict: "correct".to_string(),
+                ..rejected(1, "5", "")
+            },
+        ];
+        assert!(check(&submissions, Day(1), Part(1), "150").is_ok());
+        assert!(check(&submissions, Day(1), Part(1), "5").is_ok());
+        assert!(check(&submissions, Day(2), Part(2), "150").is_ok());
+        assert!(check(&submissions, Day(1), Part(2), "150").is_err());
+    }
+
+    #[test]
+    fn bounds_of_text_answers() {
+        let submissions = [rejected(1, "100", "Your answer is too high.")];
+        assert!(check(&submissions, Day(1), Part(1), "abc").is_ok());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::credentials;
+use crate::date::AOCDate;
+use crate::http::{self, Endpoint};
+use aoc_runner_internal::Part;
+use reqwest::StatusCode;
+use std::error;
+use std::time::Duration;
+
+/// The answer of adventofcode.com to a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The answer is wrong, with the hint given by the server (too high, too low...)
+    Incorrect(String),
+    /// The previous submission was too recent, with the remaining time to wait
+    TooRecent(String),
+    /// The part is already solved, or is not unlocked yet
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Parses the HTML page returned by the server after a submission
+    pub fn from_html(html: &str) -> Self {
+        let message = article_text(html);
+
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = if message.contains("too high") {
+                "Your answer is too high."
+            } else if message.contains("too low") {
+                "Your answer is too low."
+            } else {
+                ""
+            };
+            Verdict::Incorrect(hint.to_string())
+        } else if message.contains("You gave an answer too recently") {
+            let wait = message
+                .find("You have ")
+                .map(|start| {
+                    let rest = &message[start..];
+                    let end = rest.find(" to wait").map_or(rest.len(), |end| end + 8);
+                    rest[..end].to_string()
+                })
+                .unwrap_or_default();
+            Verdict::TooRecent(wait)
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(message)
+        }
+    }
+}
+
+impl Verdict {
+    /// Gets the name of the verdict, as stored in the submissions log
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect(_) => "incorrect",
+            Verdict::TooRecent(_) => "too-recent",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Gets the hint or message given by the server with the verdict
+    pub fn message(&self) -> &str {
+        match self {
+            Verdict::Incorrect(message)
+            | Verdict::TooRecent(message)
+            | Verdict::Unknown(message) => message,
+            Verdict::Correct | Verdict::AlreadySolved => "",
+        }
+    }
+}
+
+/// Parses the time left to wait after a submission made too recently,
+/// like `You have 1m 20s left to wait.`
+pub fn wait_time(message: &str) -> Option<Duration> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = start + message[start..].find(" left")?;
+
+    let mut seconds = 0;
+    for field in message[start..end].split_whitespace() {
+        let (value, unit) = field.split_at(field.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Extracts the text of the `<article>` element of the page, without the HTML tags
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Gets the name of the account of a session token, shown at the top of the pages
+pub fn account(date: AOCDate, token: &str) -> Option<String> {
+    let page = http::get(
+        &Endpoint::ACCOUNT,
+        &http::url(&format!("/{}", date.year)),
+        Some(token),
+    )
+    .ok()?
+    .body;
+    account_name(&page)
+}
+
+/// Reads the name of the account from a page of adventofcode.com, when logged in
+pub fn account_name(page: &str) -> Option<String> {
+    let start = page.find("<div class=\"user\">")? + "<div class=\"user\">".len();
+    let end = start + page[start..].find('<')?;
+    let name = page[start..end].trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Sends an answer to adventofcode.com, and returns the verdict of the server
+pub fn submit_answer(
+    date: AOCDate,
+    part: Part,
+    answer: &str,
+    token: &str,
+) -> Result<Verdict, Box<dyn error::Error>> {
+    let level = part.0.to_string();
+    let response = http::post(
+        &Endpoint::ANSWER,
+        &date.submit_url(),
+        token,
+        &[("level", level.as_str()), ("answer", answer)],
+    )?;
+
+    match response.status {
+        StatusCode::OK => {
+            let html = response.body;
+            if credentials::session_expired(StatusCode::OK, &html) {
+                return Err(credentials::session_expired_error().into());
+            }
+            Ok(Verdict::from_html(&html))
+        }
+        sc if credentials::session_expired(sc, "") => {
+            Err(credentials::session_expired_error().into())
+        }
+        sc => Err(format!(
+            "Could not submit the answer. Are the day, year, and token correctly set ? Status: {}",
+            sc
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn correct_answer() {
+        let html = page(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> \
+             closer to saving Christmas.",
+        );
+        assert_eq!(Verdict::from_html(&html), Verdict::Correct);
+    }
+
+    #[test]
+    fn incorrect_answer_hints() {
+        let html = page(
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure \
+             you're using the full input data.",
+        );
+        assert_eq!(
+            Verdict::from_html(&html),
+            Verdict::Incorrect("Your answer is too high.".to_string())
+        );
+
+        let html = page("That's not the right answer; your answer is too low.");
+        assert_eq!(
+            Verdict::from_html(&html),
+            Verdict::Incorrect("Your answer is too low.".to_string())
+        );
+
+        let html = page("That's not the right answer.  If you're stuck, try again.");
+        assert_eq!(Verdict::from_html(&html), Verdict::Incorrect(String::new()));
+    }
+
+    #[test]
+    fn too_recent_answer() {
+        let html = page(
+            "You gave an answer too recently; you have to wait after submitting an answer before \
+             trying again.  You have 34s left to wait. \
+             <a href=\"/2023/day/1\">[Return to Day 1]</a>",
+        );
+        assert_eq!(
+            Verdict::from_html(&html),
+            Verdict::TooRecent("You have 34s left to wait".to_string())
+        );
+    }
+
+    #[test]
+    fn wait_time_of_a_message() {
+        let seconds = |s| Some(Duration::from_secs(s));
+        assert_eq!(wait_time("You have 34s left to wait"), seconds(34));
+        assert_eq!(wait_time("You have 1m 20s left to wait."), seconds(80));
+        assert_eq!(wait_time("You have 5m left to wait."), seconds(300));
+        assert_eq!(wait_time("You have 1h 2m 3s left to wait."), seconds(3723));
+    }
+
+    #[test]
+    fn wait_time_of_other_messages() {
+        assert_eq!(wait_time(""), None);
+        assert_eq!(
+            wait_time("You have to wait after submitting an answer"),
+            None
+        );
+        assert_eq!(wait_time("You have 3 minutes left to wait."), None);
+    }
+
+    #[test]
+    fn already_solved() {
+        let html = page(
+            "You don't seem to be solving the right level.  Did you already complete it? \
+             <a href=\"/2023/day/1\">[Return to Day 1]</a>",
+        );
+        assert_eq!(Verdict::from_html(&html), Verdict::AlreadySolved);
+    }
+
+    #[test]
+    fn unknown_answer_keeps_the_text() {
+        let html = page("Something <em>unexpected</em>\n happened.");
+        assert_eq!(
+            Verdict::from_html(&html),
+            Verdict::Unknown("Something unexpected happened.".to_string())
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::Run;
+use aoc_runner_internal::{Day, Part};
+
+/// The first year of Advent of Code, telling years & days apart
+const FIRST_YEAR: u32 = 2015;
+
+/// The puzzle selected by the positional arguments of `cargo aoc`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Target {
+    pub year: Option<u32>,
+    pub day: Option<Day>,
+    pub part: Option<Part>,
+}
+
+impl Target {
+    /// Parses `[YEAR] [DAY] [PART]`, where the day & part can also be a shorthand like `d7p2`
+    pub fn parse(values: &[String]) -> Result<Self, String> {
+        let mut target = Target::default();
+
+        for value in values {
+            let value = value.to_lowercase();
+            match value.parse::<u32>() {
+                Ok(year) if year >= FIRST_YEAR => {
+                    if target.year.is_some() || target.day.is_some() {
+                        return Err(format!("Unexpected year: {}", year));
+                    }
+                    target.year = Some(year);
+                }
+                Ok(_) if target.day.is_none() => target.day = Some(value.parse()?),
+                Ok(_) if target.part.is_none() => target.part = Some(value.parse()?),
+                Ok(_) => return Err(format!("Unexpected argument: {}", value)),
+                Err(_) if target.day.is_none() => {
+                    let (day, part) = shorthand(&value)?;
+                    target.day = Some(day);
+                    target.part = part;
+                }
+                Err(_) => return Err(format!("Unexpected argument: {}", value)),
+            }
+        }
+
+        Ok(target)
+    }
+
+    /// Sets the day & part of the run, unless they are also given by flags
+    pub fn apply(self, run: &mut Run) -> Result<(), String> {
+        if let Some(year) = self.year {
+            if run.year.is_some_and(|y| y != year) {
+                return Err("The year is given twice".into());
+            }
+            run.year = Some(year);
+        }
+        if let Some(day) = self.day {
+            if run.day.is_some() {
+                return Err("The day is given twice, as an argument and with --day".into());
+            }
+            run.day = Some(day.into());
+        }
+        if let Some(part) = self.part {
+            if run.part.is_some() {
+                return Err("The part is given twice, as an argument and with --part".into());
+            }
+            run.part = Some(part);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a shorthand day & part, like `d7`, `d7p2` or `day7part2`
+fn shorthand(value: &str) -> Result<(Day, Option<Part>), String> {
+    let day = value
+        .strip_prefix("day")
+        .or_else(|| value.strip_prefix('d'))
+        .ok_or_else(|| format!("Invalid day: {}", value))?;
+
+    match day.split_once("part").or_else(|| day.split_once('p')) {
+        Some((day, part)) => Ok((day.parse()?, Some(part.parse()?))),
+        None => Ok((day.parse()?, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(values: &[&str]) -> Result<Target, String> {
+        let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        Target::parse(&values)
+    }
+
+    fn target(year: Option<u32>, day: Option<u8>, part: Option<u8>) -> Target {
+        Target {
+            year,
+            day: day.map(Day),
+            part: part.map(Part),
+        }
+    }
+
+    #[test]
+    fn parse_numbers() {
+        assert_eq!(parse(&[]), Ok(Target::default()));
+        assert_eq!(parse(&["7"]), Ok(target(None, Some(7), None)));
+        assert_eq!(parse(&["7", "2"]), Ok(target(None, Some(7), Some(2))));
+        assert_eq!(parse(&["2018"]), Ok(target(Some(2018), None, None)));
+        assert_eq!(
+            parse(&["2018", "7", "2"]),
+            Ok(target(Some(2018), Some(7), Some(2)))
+        );
+    }
+
+    #[test]
+    fn parse_shorthands() {
+        assert_eq!(parse(&["d7"]), Ok(target(None, Some(7), None)));
+        assert_eq!(parse(&["d7p2"]), Ok(target(None, Some(7), Some(2))));
+        assert_eq!(parse(&["Day7Part2"]), Ok(target(None, Some(7), Some(2))));
+        assert_eq!(
+            parse(&["2025", "d12p1"]),
+            Ok(target(Some(2025), Some(12), Some(1)))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_arguments() {
+        assert!(parse(&["7", "2018"]).is_err());
+        assert!(parse(&["2018", "2019"]).is_err());
+        assert!(parse(&["7", "2", "1"]).is_err());
+        assert!(parse(&["d7", "d8"]).is_err());
+        assert!(parse(&["7", "3"]).is_err());
+        assert!(parse(&["26"]).is_err());
+        assert!(parse(&["x7"]).is_err());
+        assert!(parse(&["d7p"]).is_err());
+    }
+
+    #[test]
+    fn apply_to_a_run() {
+        let mut run = Run::default();
+        target(Some(2018), Some(7), Some(2))
+            .apply(&mut run)
+            .unwrap();
+        assert_eq!(run.year, Some(2018));
+        assert_eq!(run.day, Some(Day(7).into()));
+        assert_eq!(run.part, Some(Part(2)));
+    }
+
+    #[test]
+    fn apply_twice() {
+        let mut run = Run {
+            year: Some(2018),
+            day: Some(Day(7).into()),
+            ..Run::default()
+        };
+        assert!(target(Some(2018), None, None).apply(&mut run).is_ok());
+        assert!(target(Some(2019), None, None).apply(&mut run).is_err());
+        assert!(target(None, Some(7), None).apply(&mut run).is_err());
+
+        run.part = Some(Part(1));
+        assert!(target(None, None, Some(2)).apply(&mut run).is_err());
+    }
+}
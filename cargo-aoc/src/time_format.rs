@@ -0,0 +1,99 @@
+use clap::ValueEnum;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// The unit used to print durations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimeUnit {
+    /// The most readable unit for each duration
+    #[default]
+    Auto,
+    Ns,
+    Us,
+    Ms,
+    S,
+}
+
+impl TimeUnit {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            TimeUnit::Auto | TimeUnit::Ns => "ns",
+            TimeUnit::Us => "µs",
+            TimeUnit::Ms => "ms",
+            TimeUnit::S => "s",
+        }
+    }
+
+    /// Gets the name of the unit in ascii, used in column names
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeUnit::Us => "us",
+            unit => unit.suffix(),
+        }
+    }
+
+    /// The number of nanoseconds in this unit
+    fn nanos(self) -> f64 {
+        match self {
+            TimeUnit::Auto | TimeUnit::Ns => 1.,
+            TimeUnit::Us => 1e3,
+            TimeUnit::Ms => 1e6,
+            TimeUnit::S => 1e9,
+        }
+    }
+
+    /// Converts a duration to this unit, `auto` being nanoseconds
+    pub fn value(self, duration: Duration) -> f64 {
+        duration.as_nanos() as f64 / self.nanos()
+    }
+}
+
+static FORMAT: OnceLock<(TimeUnit, Option<usize>)> = OnceLock::new();
+
+/// Sets the unit & the number of decimals of the durations, must be called before printing anything
+pub fn init(unit: TimeUnit, precision: Option<usize>) {
+    let _ = FORMAT.set((unit, precision));
+}
+
+pub fn unit() -> TimeUnit {
+    FORMAT.get().map_or(TimeUnit::Auto, |f| f.0)
+}
+
+pub fn precision() -> Option<usize> {
+    FORMAT.get().and_then(|f| f.1)
+}
+
+/// Formats a duration with the chosen unit & precision, 2 decimals by default
+pub fn format(duration: Duration) -> String {
+    let precision = precision().unwrap_or(2);
+    match unit() {
+        TimeUnit::Auto => format!("{:.precision$?}", duration),
+        unit => format!("{:.precision$}{}", unit.value(duration), unit.suffix()),
+    }
+}
+
+/// Formats a duration as a number in the chosen unit, nanoseconds by default
+pub fn number(duration: Duration) -> String {
+    match (unit(), precision()) {
+        (TimeUnit::Auto, _) => duration.as_nanos().to_string(),
+        (unit, precision) => format!("{:.*}", precision.unwrap_or(2), unit.value(duration)),
+    }
+}
+
+/// Gets the body of the `format_time` function of the generated runner, formatting `duration`.
+///
+/// Without any choice, the full precision is kept.
+pub fn template() -> String {
+    match (unit(), precision()) {
+        (TimeUnit::Auto, None) => "format!(\"{:?}\", duration)".to_string(),
+        (TimeUnit::Auto, Some(precision)) => {
+            format!("format!(\"{{:.{}?}}\", duration)", precision)
+        }
+        (unit, precision) => format!(
+            "format!(\"{{:.{}}}{}\", duration.as_nanos() as f64 / {:?})",
+            precision.unwrap_or(2),
+            unit.suffix(),
+            unit.nanos()
+        ),
+    }
+}
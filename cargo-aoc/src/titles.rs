@@ -0,0 +1,64 @@
+use crate::credentials::CredentialsManager;
+use crate::date::AOCDate;
+use crate::http::{self, Endpoint};
+use crate::puzzle;
+use aoc_runner_internal::Day;
+
+/// Gets the title of a puzzle, like `Day 7: The Sum of Its Parts`, from its cached description
+pub fn get(year: u32, day: Day) -> Option<String> {
+    let date = AOCDate {
+        day: u32::from(day.0),
+        year: year as i32,
+    };
+    let description = puzzle::cached(date)?;
+
+    let start = description.find("<h2>")? + "<h2>".len();
+    let end = start + description[start..].find("</h2>")?;
+    let title = puzzle::text(&description[start..end]);
+
+    Some(title.trim_matches(|c: char| c == '-' || c.is_whitespace()).to_string())
+}
+
+/// Gets the name of a puzzle, the title without the day
+pub fn name(year: u32, day: Day) -> Option<String> {
+    let title = get(year, day)?;
+    Some(match title.split_once(": ") {
+        Some((_, name)) => name.to_string(),
+        None => title,
+    })
+}
+
+/// Downloads the descriptions of the days without a title yet, to cache their titles.
+///
+/// The titles are only a nicety : the days are shown without them after the first error, like
+/// without network, rather than waiting for every download to fail.
+pub fn fetch(year: u32, days: &[Day]) {
+    if http::offline() || days.iter().all(|&day| get(year, day).is_some()) {
+        return;
+    }
+    let token = CredentialsManager::new().get_session_token().ok();
+
+    for &day in days {
+        if get(year, day).is_none() {
+            let date = AOCDate {
+                day: u32::from(day.0),
+                year: year as i32,
+            };
+            if puzzle::load_from(&Endpoint::TITLE, date, token.as_deref(), false).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Gets the names of the criterion group of a day, the one with the title of the puzzle first,
+/// then the one used before it was known.
+///
+/// They start with `day{n}`, to filter the benchmarks of a day with `cargo bench day7`.
+pub fn bench_groups(year: u32, day: Day) -> Vec<String> {
+    let group = format!("day{}", day.0);
+    match name(year, day) {
+        Some(name) => vec![format!("{} - {}", group, name), group],
+        None => vec![group],
+    }
+}
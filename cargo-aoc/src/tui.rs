@@ -0,0 +1,351 @@
+use crate::calendar;
+use crate::results::{PartResult, Status};
+use crate::time_format;
+use aoc_runner_internal::{Day, DayParts, Part};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::{self, Read};
+use std::process;
+
+/// A key pressed in the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    /// Ctrl-C, read as a key while the terminal is in raw mode
+    Interrupt,
+    Char(char),
+}
+
+/// Reads a key, the arrows being sent by the terminal as escape sequences
+pub fn read_key(input: &mut impl Read) -> io::Result<Option<Key>> {
+    let mut byte = [0];
+    if input.read(&mut byte)? == 0 {
+        return Ok(None);
+    }
+
+    let key = match byte[0] {
+        b'\r' | b'\n' => Key::Enter,
+        3 => Key::Interrupt,
+        0x1b => {
+            let mut sequence = [0; 2];
+            input.read_exact(&mut sequence)?;
+            match sequence {
+                [b'[' | b'O', b'A'] => Key::Up,
+                [b'[' | b'O', b'B'] => Key::Down,
+                [b'[' | b'O', b'C'] => Key::Right,
+                [b'[' | b'O', b'D'] => Key::Left,
+                [_, c] => Key::Char(char::from(c)),
+            }
+        }
+        c => Key::Char(char::from(c).to_ascii_lowercase()),
+    };
+    Ok(Some(key))
+}
+
+/// An action of the dashboard, chosen with a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Runs a day, or every implemented day
+    Run(Option<Day>),
+    Bench(Day),
+    Quit,
+}
+
+/// Puts the terminal in raw mode while it lives, for the keys to be read as soon as they are
+/// pressed, without being echoed
+pub struct RawMode {
+    #[cfg(unix)]
+    saved: String,
+    #[cfg(windows)]
+    saved: u32,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    /// Enables the raw mode with stty, `None` when the standard input isn't a terminal
+    pub fn enable() -> Option<Self> {
+        let saved = stty(&["-g"])?;
+        // The output is still processed, and Ctrl-C is read as a key to restore the terminal
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        Some(RawMode {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Runs stty on the terminal of the standard input, and gets its output
+#[cfg(unix)]
+fn stty(args: &[&str]) -> Option<String> {
+    let output = process::Command::new("stty")
+        .args(args)
+        .stdin(process::Stdio::inherit())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(windows)]
+mod console {
+    pub const STD_INPUT_HANDLE: u32 = -10i32 as u32;
+    pub const ENABLE_PROCESSED_INPUT: u32 = 0x1;
+    pub const ENABLE_LINE_INPUT: u32 = 0x2;
+    pub const ENABLE_ECHO_INPUT: u32 = 0x4;
+    /// Sends the arrows as escape sequences, like the terminals of the other systems
+    pub const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x200;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn GetStdHandle(handle: u32) -> isize;
+        pub fn GetConsoleMode(console: isize, mode: *mut u32) -> i32;
+        pub fn SetConsoleMode(console: isize, mode: u32) -> i32;
+    }
+}
+
+#[cfg(windows)]
+impl RawMode {
+    /// Enables the raw mode of the console, `None` when the standard input isn't a console
+    pub fn enable() -> Option<Self> {
+        use console::*;
+
+        let mut saved = 0;
+        // SAFETY: the handle of the standard input is valid for the whole process
+        unsafe {
+            let input = GetStdHandle(STD_INPUT_HANDLE);
+            if GetConsoleMode(input, &mut saved) == 0 {
+                return None;
+            }
+            let raw = (saved & !(ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT))
+                | ENABLE_VIRTUAL_TERMINAL_INPUT;
+            if SetConsoleMode(input, raw) == 0 {
+                return None;
+            }
+        }
+        Some(RawMode { saved })
+    }
+}
+
+#[cfg(windows)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: the handle of the standard input is valid for the whole process
+        unsafe {
+            console::SetConsoleMode(console::GetStdHandle(console::STD_INPUT_HANDLE), self.saved);
+        }
+    }
+}
+
+/// The state of the dashboard : the implemented parts, and the last results of each of them
+pub struct Dashboard {
+    year: u32,
+    /// The number of days of the event
+    days: u8,
+    implemented: BTreeMap<Day, Vec<Part>>,
+    results: BTreeMap<(Day, Part, Option<String>), PartResult>,
+    message: Option<String>,
+    /// The day chosen with the arrows
+    selected: Day,
+}
+
+impl Dashboard {
+    pub fn new(day_parts: &DayParts) -> Self {
+        let mut dashboard = Dashboard {
+            year: day_parts.year,
+            days: calendar::days(day_parts.year as i32) as u8,
+            implemented: BTreeMap::new(),
+            results: BTreeMap::new(),
+            message: None,
+            selected: Day(1),
+        };
+        dashboard.set_implemented(day_parts);
+        // The last implemented day is the one being worked on
+        if let Some(&day) = dashboard.implemented.keys().next_back() {
+            dashboard.selected = day;
+        }
+        dashboard
+    }
+
+    /// Handles a key, moving the selected day in the calendar, or choosing a command
+    pub fn handle(&mut self, key: Key) -> Option<Command> {
+        let day = self.selected.0 as i8;
+        let moved = match key {
+            Key::Left | Key::Char('h') => day - 1,
+            Key::Right | Key::Char('l') => day + 1,
+            Key::Up | Key::Char('k') => day - 5,
+            Key::Down | Key::Char('j') => day + 5,
+            Key::Enter | Key::Char('r') => return Some(Command::Run(Some(self.selected))),
+            Key::Char('a') => return Some(Command::Run(None)),
+            Key::Char('b') => return Some(Command::Bench(self.selected)),
+            Key::Char('q') | Key::Interrupt => return Some(Command::Quit),
+            Key::Char(c) => {
+                self.message = Some(format!("Unknown key: {}", c.escape_debug()));
+                return None;
+            }
+        };
+        if (1..=self.days as i8).contains(&moved) {
+            self.selected = Day(moved as u8);
+        }
+        None
+    }
+
+    pub fn set_implemented(&mut self, day_parts: &DayParts) {
+        self.implemented.clear();
+        for dp in day_parts.iter() {
+            let parts = self.implemented.entry(dp.day).or_default();
+            if !parts.contains(&dp.part) {
+                parts.push(dp.part);
+            }
+        }
+    }
+
+    pub fn update(&mut self, results: Vec<PartResult>) {
+        for result in results {
+            self.results
+                .insert((result.day, result.part, result.name.clone()), result);
+        }
+        self.message = None;
+    }
+
+    pub fn set_message(&mut self, message: String) {
+        self.message = Some(message);
+    }
+
+    /// The status of a part in the calendar :
+    /// `*` solved, `x` failed, `o` implemented but not run yet, `.` not implemented
+    fn part_status(&self, day: Day, part: Part) -> char {
+        let mut results = self
+            .results
+            .values()
+            .filter(|r| r.day == day && r.part == part)
+            .peekable();
+
+        if results.peek().is_some() {
+            if results.all(|r| r.status == Status::Ok) {
+                '*'
+            } else {
+                'x'
+            }
+        } else if self
+            .implemented
+            .get(&day)
+            .is_some_and(|parts| parts.contains(&part))
+        {
+            'o'
+        } else {
+            '.'
+        }
+    }
+
+    pub fn render(&self) -> String {
+        // Clears the terminal before drawing
+        let mut out = String::from("\x1b[2J\x1b[H");
+
+        let _ = writeln!(out, "AOC {}\n", self.year);
+        for week in 0..self.days.div_ceil(5) {
+            let week = (1..=5u8).map(|d| Day(week * 5 + d));
+            for day in week.filter(|day| day.0 <= self.days) {
+                let cell = format!(
+                    "{:>2} {}{}",
+                    day.0,
+                    self.part_status(day, Part(1)),
+                    self.part_status(day, Part(2))
+                );
+                if day == self.selected {
+                    // Shown in reverse video
+                    let _ = write!(out, "  \x1b[7m{}\x1b[0m  ", cell);
+                } else {
+                    let _ = write!(out, "  {}  ", cell);
+                }
+            }
+            out.push('\n');
+        }
+        out += "\n  * solved   x failed   o implemented   . not implemented\n\n";
+
+        for result in self.results.values() {
+            let _ = match result.status {
+                Status::Ok => writeln!(
+                    out,
+                    "{} : {}\n\tgenerator: {}, runner: {}",
+                    result.display(),
+                    result.answer,
+                    time_format::format(result.generator),
+                    time_format::format(result.runner)
+                ),
+                _ => writeln!(out, "{} : FAILED, {}", result.display(), result.answer),
+            };
+        }
+
+        if let Some(message) = &self.message {
+            let _ = writeln!(out, "\n{}", message);
+        }
+
+        let _ = write!(
+            out,
+            "\narrows select a day ({}) | enter/r run it | a run all | b bench it | q quit",
+            self.selected.0
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_runner_internal::{DayPart, DayPartsBuilder};
+
+    fn new_dashboard(year: u32, days: &[u8]) -> Dashboard {
+        let day_parts = days
+            .iter()
+            .map(|&day| DayPart {
+                day: Day(day),
+                part: Part(1),
+                name: None,
+            })
+            .collect::<DayPartsBuilder>()
+            .with_year(year);
+        Dashboard::new(&day_parts)
+    }
+
+    #[test]
+    fn moves_within_the_days_of_the_year() {
+        let mut dashboard = new_dashboard(2025, &[11]);
+        assert_eq!(dashboard.selected, Day(11));
+
+        assert_eq!(dashboard.handle(Key::Right), None);
+        assert_eq!(dashboard.selected, Day(12));
+        dashboard.handle(Key::Right);
+        assert_eq!(dashboard.selected, Day(12));
+        dashboard.handle(Key::Down);
+        assert_eq!(dashboard.selected, Day(12));
+        dashboard.handle(Key::Char('k'));
+        assert_eq!(dashboard.selected, Day(7));
+
+        let mut dashboard = new_dashboard(2024, &[12]);
+        dashboard.handle(Key::Down);
+        dashboard.handle(Key::Down);
+        assert_eq!(dashboard.selected, Day(22));
+    }
+
+    #[test]
+    fn renders_the_days_of_the_year() {
+        let render = new_dashboard(2025, &[1]).render();
+        assert!(render.contains("12 .."));
+        assert!(!render.contains("13 .."));
+
+        let render = new_dashboard(2024, &[1]).render();
+        assert!(render.contains("25 .."));
+    }
+}
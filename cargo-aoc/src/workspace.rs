@@ -0,0 +1,268 @@
+use crate::project::ProjectManager;
+use serde::Deserialize;
+use std::env;
+use std::error;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+
+/// The part of the output of `cargo metadata` used to find the members of a workspace
+#[derive(Deserialize, Debug)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_root: PathBuf,
+}
+
+#[derive(Deserialize, Debug)]
+struct Package {
+    name: String,
+    manifest_path: PathBuf,
+    dependencies: Vec<Dependency>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Dependency {
+    name: String,
+}
+
+impl Package {
+    fn dir(&self) -> &Path {
+        self.manifest_path.parent().unwrap_or(Path::new("."))
+    }
+
+    /// Tells if the package holds solutions, depending on aoc-runner
+    fn is_solution(&self) -> bool {
+        self.dependencies.iter().any(|d| d.name == "aoc-runner")
+    }
+}
+
+impl Metadata {
+    /// Picks the crate of the solutions: the given member, the one containing the current
+    /// directory, or the only member depending on aoc-runner
+    fn select(
+        &self,
+        package: Option<&str>,
+        current_dir: &Path,
+    ) -> Result<Option<&Package>, String> {
+        if let Some(name) = package {
+            return self
+                .packages
+                .iter()
+                .find(|p| p.name == name)
+                .map(Some)
+                .ok_or_else(|| {
+                    format!(
+                        "No package {} in the workspace, the members are: {}",
+                        name,
+                        names(self.packages.iter())
+                    )
+                });
+        }
+
+        let containing = self
+            .packages
+            .iter()
+            .filter(|p| current_dir.starts_with(p.dir()))
+            .max_by_key(|p| p.dir().components().count());
+        let solutions: Vec<_> = self.packages.iter().filter(|p| p.is_solution()).collect();
+
+        match (containing, solutions.as_slice()) {
+            (Some(package), _) => Ok(Some(package)),
+            (None, [package]) => Ok(Some(package)),
+            // A virtual manifest without solutions, the commands report the missing crate
+            (None, []) => Ok(None),
+            (None, solutions) => Err(format!(
+                "Several crates of the workspace hold solutions, pick one with --package or --year: {}",
+                names(solutions.iter().copied())
+            )),
+        }
+    }
+}
+
+static WORKSPACE_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Gets the root of the workspace of the project, once entered
+pub fn root() -> Option<&'static Path> {
+    WORKSPACE_ROOT.get().map(PathBuf::as_path)
+}
+
+/// Moves to the directory of the crate holding the solutions, like if cargo-aoc was run from it.
+///
+/// The crate is the given member of the workspace, the one holding the solutions of the given
+/// year, the one containing the current directory, or the only member depending on aoc-runner.
+/// Outside of a cargo project, nothing is done.
+pub fn enter_project(
+    package: Option<&str>,
+    year: Option<u32>,
+) -> Result<(), Box<dyn error::Error>> {
+    let Some(metadata) = metadata(package.is_some() || year.is_some())? else {
+        return Ok(());
+    };
+    let _ = WORKSPACE_ROOT.set(metadata.workspace_root.clone());
+
+    if let (None, Some(year)) = (package, year) {
+        return enter_year(&metadata, year);
+    }
+
+    let current_dir = env::current_dir()?;
+    if let Some(package) = metadata.select(package, &current_dir)? {
+        env::set_current_dir(package.dir())?;
+    }
+
+    Ok(())
+}
+
+/// Moves to the crate holding the solutions of a year, checking each crate to read its year
+fn enter_year(metadata: &Metadata, year: u32) -> Result<(), Box<dyn error::Error>> {
+    for package in metadata.packages.iter().filter(|p| p.is_solution()) {
+        env::set_current_dir(package.dir())?;
+        if project_year()? == year {
+            return Ok(());
+        }
+    }
+
+    Err(format!("No crate of the workspace holds the solutions of {}", year).into())
+}
+
+/// Gets the directories of the crates holding solutions in the workspace, sorted by year.
+///
+/// Each crate is checked to read its year, so the current directory is changed.
+pub fn solution_crates() -> Result<Vec<(u32, PathBuf)>, Box<dyn error::Error>> {
+    let metadata = metadata(true)?.ok_or("Not in a cargo project")?;
+    let _ = WORKSPACE_ROOT.set(metadata.workspace_root.clone());
+
+    let mut crates = Vec::new();
+    for package in metadata.packages.iter().filter(|p| p.is_solution()) {
+        env::set_current_dir(package.dir())?;
+        crates.push((project_year()?, package.dir().to_path_buf()));
+    }
+    crates.sort();
+
+    Ok(crates)
+}
+
+/// Checks the crate of the current directory, to get the year of its solutions
+fn project_year() -> Result<u32, Box<dyn error::Error>> {
+    let mut pm = ProjectManager::new()?;
+    pm.quiet = true;
+    Ok(pm.build_project()?.year)
+}
+
+/// Runs `cargo metadata`, its errors being reported only when a package is required
+fn metadata(required: bool) -> Result<Option<Metadata>, Box<dyn error::Error>> {
+    let output = process::Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .stderr(if required {
+            process::Stdio::inherit()
+        } else {
+            process::Stdio::null()
+        })
+        .output()?;
+
+    if output.status.success() {
+        Ok(Some(serde_json::from_slice(&output.stdout)?))
+    } else if required {
+        Err(format!(
+            "cargo metadata failed with code {}",
+            output.status.code().unwrap_or(-1)
+        )
+        .into())
+    } else {
+        Ok(None)
+    }
+}
+
+fn names<'a>(packages: impl Iterator<Item = &'a Package>) -> String {
+    packages
+        .map(|p| p.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, dir: &str, solution: bool) -> Package {
+        let dependencies = if solution {
+            vec![Dependency {
+                name: "aoc-runner".to_string(),
+            }]
+        } else {
+            Vec::new()
+        };
+        Package {
+            name: name.to_string(),
+            manifest_path: Path::new(dir).join("Cargo.toml"),
+            dependencies,
+        }
+    }
+
+    fn workspace(packages: Vec<Package>) -> Metadata {
+        Metadata {
+            packages,
+            workspace_root: PathBuf::from("/aoc"),
+        }
+    }
+
+    fn select<'a>(
+        metadata: &'a Metadata,
+        package: Option<&str>,
+        current_dir: &str,
+    ) -> Result<Option<&'a str>, String> {
+        let package = metadata.select(package, Path::new(current_dir))?;
+        Ok(package.map(|p| p.name.as_str()))
+    }
+
+    #[test]
+    fn select_the_given_package() {
+        let metadata = workspace(vec![
+            package("aoc-2024", "/aoc/2024", true),
+            package("aoc-2025", "/aoc/2025", true),
+        ]);
+        assert_eq!(
+            select(&metadata, Some("aoc-2025"), "/aoc/2024/src"),
+            Ok(Some("aoc-2025"))
+        );
+        assert!(select(&metadata, Some("aoc-2026"), "/aoc")
+            .unwrap_err()
+            .contains("aoc-2024, aoc-2025"));
+    }
+
+    #[test]
+    fn select_the_package_of_the_current_directory() {
+        let metadata = workspace(vec![
+            package("aoc", "/aoc", false),
+            package("aoc-2024", "/aoc/2024", true),
+            package("aoc-2025", "/aoc/2025", true),
+        ]);
+        assert_eq!(
+            select(&metadata, None, "/aoc/2025/src"),
+            Ok(Some("aoc-2025"))
+        );
+        assert_eq!(select(&metadata, None, "/aoc/utils"), Ok(Some("aoc")));
+    }
+
+    #[test]
+    fn select_the_only_solutions() {
+        let metadata = workspace(vec![
+            package("utils", "/aoc/utils", false),
+            package("aoc-2025", "/aoc/2025", true),
+        ]);
+        assert_eq!(select(&metadata, None, "/aoc"), Ok(Some("aoc-2025")));
+
+        let metadata = workspace(vec![package("utils", "/aoc/utils", false)]);
+        assert_eq!(select(&metadata, None, "/aoc"), Ok(None));
+    }
+
+    #[test]
+    fn select_among_several_solutions() {
+        let metadata = workspace(vec![
+            package("aoc-2024", "/aoc/2024", true),
+            package("aoc-2025", "/aoc/2025", true),
+        ]);
+        assert!(select(&metadata, None, "/aoc")
+            .unwrap_err()
+            .contains("--package or --year: aoc-2024, aoc-2025"));
+    }
+}
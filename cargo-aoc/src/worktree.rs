@@ -0,0 +1,65 @@
+//! Checks out another revision of the project in a temporary git worktree, to benchmark it
+//! with `cargo aoc bench --compare-ref`.
+
+use std::env;
+use std::error;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A detached worktree, removed when dropped
+pub struct Worktree {
+    root: PathBuf,
+    /// The path of the project in the repository, when it is not at its root
+    prefix: String,
+}
+
+impl Worktree {
+    /// Checks out a revision (a branch, a tag or a commit) of the repository of the project
+    pub fn add(reference: &str) -> Result<Self, Box<dyn error::Error>> {
+        let prefix = git(&["rev-parse", "--show-prefix"])
+            .map_err(|e| format!("The project must be in a git repository ({})", e))?;
+        git(&["rev-parse", "--verify", "--quiet", reference])
+            .map_err(|_| format!("Unknown git revision: {}", reference))?;
+
+        let root = env::temp_dir().join(format!("cargo-aoc-compare-{}", std::process::id()));
+        let path = root.display().to_string();
+        git(&["worktree", "add", "--detach", &path, reference])?;
+
+        Ok(Worktree {
+            root,
+            prefix: prefix.trim().to_string(),
+        })
+    }
+
+    /// Gets the directory of the project in the worktree
+    pub fn project_dir(&self) -> PathBuf {
+        self.root.join(&self.prefix)
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let path = self.root.display().to_string();
+        if git(&["worktree", "remove", "--force", &path]).is_err() {
+            eprintln!("Could not remove the git worktree {}", path);
+        }
+    }
+}
+
+/// Runs git, and returns its standard output
+pub fn git(args: &[&str]) -> Result<String, Box<dyn error::Error>> {
+    let output = Command::new("git")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Could not run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
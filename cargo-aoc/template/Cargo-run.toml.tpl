@@ -4,11 +4,14 @@ version = "0.3.0"
 authors = ["Grégory Obanos <gregory.obanos@gmail.com>"]
 edition = "2021"
 
+# Not a member of the workspace of the project
+[workspace]
+
 [dependencies]
-{CRATE_NAME} = { path = "../../.." }
+{CRATE_NAME} = { path = "../../.."{FEATURES} }
 
 # For release
-aoc-runner = "0.3"
+aoc-runner = "{RUNNER_VERSION}"
 # For dev
 # aoc-runner = { path = "../../../../aoc-runner" }
 
@@ -27,5 +27,9 @@ fn input_benchmark(c: &mut Criterion) {
     {GENS}
 }
 
-criterion_group!(benches, {BENCHMARKS});
+criterion_group! {
+    name = benches;
+    config = {CONFIG};
+    targets = {BENCHMARKS}
+}
 criterion_main!(benches);
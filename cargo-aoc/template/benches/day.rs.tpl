@@ -0,0 +1,4 @@
+
+    let mut group = c.benchmark_group({GROUP});
+{PARTS}
+    group.finish();
\ No newline at end of file
@@ -0,0 +1,19 @@
+extern crate {CRATE_SLUG};
+extern crate aoc_runner;
+extern crate divan;
+
+use {CRATE_SLUG}::*;
+use aoc_runner::ArcStr;
+use divan::Bencher;
+use std::fmt::Display;
+
+#[inline]
+fn black_box(t: &dyn Display) {
+    divan::black_box(t);
+}
+
+fn main() {
+    divan::main();
+}
+{PARTS}
+{GENS}
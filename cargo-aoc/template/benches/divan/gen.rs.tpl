@@ -0,0 +1,6 @@
+
+/// {GROUP}
+mod {GEN_NAME}_part{PART}_generator {
+    use super::*;
+    {IMPLS}
+}
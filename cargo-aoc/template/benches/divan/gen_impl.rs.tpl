@@ -0,0 +1,6 @@
+
+    #[divan::bench(name = "{NAME}")]
+    fn {RUNNER_NAME}(bencher: Bencher) {
+        {INPUTS}
+        bencher.bench_local(|| Factory::{RUNNER_NAME}(input_day{DAY}.clone()).unwrap());
+    }
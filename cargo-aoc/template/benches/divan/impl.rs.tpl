@@ -0,0 +1,8 @@
+
+    #[divan::bench(name = "{NAME}")]
+    fn {RUNNER_NAME}(bencher: Bencher) {
+        {INPUTS}
+        let runner = Factory::{RUNNER_NAME}(input_day{DAY}.clone())
+            .expect("failed to generate input for {NAME}");
+        bencher.bench_local(|| runner.bench(black_box));
+    }
@@ -0,0 +1,6 @@
+
+/// {GROUP}
+mod {PART_NAME} {
+    use super::*;
+    {IMPLS}
+}
@@ -1,6 +1 @@
-
-    let mut group = c.benchmark_group("Generator Day{DAY} - Part{PART}");
-
-    {IMPLS}
-
-    group.finish();
+{IMPLS}
\ No newline at end of file
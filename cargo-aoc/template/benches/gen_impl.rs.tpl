@@ -1,5 +1,5 @@
 
     {
         let input = input_day{DAY}.clone();
-        group.bench_function("{NAME}", move |b| b.iter(|| Factory::{RUNNER_NAME}(input.clone()).unwrap()));
+        group.bench_function("{BENCH_ID}", move |b| b.iter(|| Factory::{RUNNER_NAME}(input.clone()).unwrap()));
     }
\ No newline at end of file
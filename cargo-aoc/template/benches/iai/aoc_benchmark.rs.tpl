@@ -0,0 +1,20 @@
+extern crate {CRATE_SLUG};
+extern crate aoc_runner;
+extern crate iai_callgrind;
+
+use {CRATE_SLUG}::*;
+use aoc_runner::{ArcStr, Runner};
+use iai_callgrind::{library_benchmark, library_benchmark_group, main};
+use std::error::Error;
+use std::fmt::Display;
+
+type Generator = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+#[inline]
+fn black_box(t: &dyn Display) {
+    std::hint::black_box(t);
+}
+{PARTS}
+{GENS}
+library_benchmark_group!(name = aoc_benchmark; benchmarks = {FUNCTIONS});
+main!(library_benchmark_groups = aoc_benchmark);
@@ -0,0 +1,6 @@
+
+/// {GROUP}
+#[library_benchmark]{IMPLS}
+fn {GEN_NAME}_part{PART}_generator((generator, input): (Generator, ArcStr)) -> Box<dyn Runner> {
+    generator(input).unwrap()
+}
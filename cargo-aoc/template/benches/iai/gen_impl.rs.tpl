@@ -0,0 +1,5 @@
+
+#[bench::{RUNNER_NAME}({
+    {INPUTS}
+    (Factory::{RUNNER_NAME} as Generator, input_day{DAY})
+})]
\ No newline at end of file
@@ -0,0 +1,5 @@
+
+#[bench::{RUNNER_NAME}({
+    {INPUTS}
+    Factory::{RUNNER_NAME}(input_day{DAY}).expect("failed to generate input for {NAME}")
+})]
\ No newline at end of file
@@ -0,0 +1,6 @@
+
+/// {GROUP}
+#[library_benchmark]{IMPLS}
+fn {PART_NAME}(runner: Box<dyn Runner>) {
+    runner.bench(black_box);
+}
@@ -1,6 +1 @@
-
-    let mut group = c.benchmark_group("Day{DAY} - Part{PART}");
-
-    {IMPLS}
-
-    group.finish();
\ No newline at end of file
+{IMPLS}
\ No newline at end of file
@@ -0,0 +1,8 @@
+[package]
+name = "{CRATE_NAME}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc-runner = "0.3"
+aoc-runner-derive = "0.3"
@@ -0,0 +1,3 @@
+use aoc_runner_derive::aoc_lib;
+
+aoc_lib! { year = {YEAR} }
@@ -0,0 +1,3 @@
+use aoc_runner_derive::aoc_main;
+
+aoc_main! { lib = {CRATE_SLUG} }
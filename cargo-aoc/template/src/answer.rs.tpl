@@ -0,0 +1,9 @@
+
+    {
+        let runner = Factory::{RUNNER_NAME}(input_day{DAY}.clone())
+            .expect("{RUNNER_DISPLAY} : FAILED while generating");
+        let result = runner
+            .try_run()
+            .expect("{RUNNER_DISPLAY} : FAILED while running");
+        println!("\x1e{}", result.to_string().escape_debug());
+    }
\ No newline at end of file
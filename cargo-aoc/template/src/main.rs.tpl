@@ -2,11 +2,153 @@ extern crate {CRATE_SLUG};
 extern crate aoc_runner;
 
 use {CRATE_SLUG}::*;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+#[allow(unused_imports)]
+use std::panic::{self, AssertUnwindSafe};
 use aoc_runner::ArcStr;
 
+#[allow(dead_code)]
+const COLOR: bool = {COLOR};
+
+#[allow(dead_code)]
+fn paint(style: &str, text: &dyn std::fmt::Display) -> String {
+    if COLOR {
+        format!("\x1b[{}m{}\x1b[0m", style, text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[allow(dead_code)]
+fn format_time(duration: Duration) -> String {
+    {FORMAT_TIME}
+}
+
+#[allow(dead_code)]
+fn paint_time(duration: Duration) -> String {
+    let style = if duration < Duration::from_millis(10) {
+        "32"
+    } else if duration < Duration::from_secs(1) {
+        "33"
+    } else {
+        "31"
+    };
+    paint(style, &format_time(duration))
+}
+
+/// The solution currently running, and when it started
+static RUNNING: std::sync::Mutex<Option<(&str, Instant)>> = std::sync::Mutex::new(None);
+static SPINNER_SHOWN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Starts a thread showing a spinner with the elapsed time (and the progress reported by the
+/// solution) on the standard error, when a solution runs for more than a second
+fn start_spinner() {
+    use std::io::IsTerminal;
+    use std::sync::atomic::Ordering;
+
+    if std::env::var_os("CARGO_AOC_NO_SPINNER").is_some() || !std::io::stderr().is_terminal() {
+        return;
+    }
+
+    std::thread::spawn(|| {
+        for frame in ['|', '/', '-', '\\'].iter().cycle() {
+            std::thread::sleep(Duration::from_millis(100));
+
+            let running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some((label, start)) = *running {
+                if start.elapsed() >= Duration::from_secs(1) {
+                    let progress = match progress::current() {
+                        Some((current, total)) => format!(" ({:.1}%)", current as f64 * 100. / total as f64),
+                        None => String::new(),
+                    };
+                    eprint!("\r{} {} : {:.1?}{}\x1b[K", frame, label, start.elapsed(), progress);
+                    SPINNER_SHOWN.store(true, Ordering::SeqCst);
+                }
+            }
+        }
+    });
+}
+
+/// Runs `f`, letting the spinner know about it.
+/// A single solution is tracked when they run in parallel.
+#[allow(dead_code)]
+fn with_spinner<T>(label: &'static str, f: impl FnOnce() -> T) -> T {
+    use std::sync::atomic::Ordering;
+
+    // Clears the spinner, even if the solution panics
+    struct Done;
+    impl Drop for Done {
+        fn drop(&mut self) {
+            *RUNNING.lock().unwrap_or_else(|e| e.into_inner()) = None;
+            progress::reset();
+            if SPINNER_SHOWN.swap(false, Ordering::SeqCst) {
+                eprint!("\r\x1b[K");
+            }
+        }
+    }
+
+    let tracked = {
+        let mut running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+        running.is_none() && running.replace((label, Instant::now())).is_none()
+    };
+    if !tracked {
+        return f();
+    }
+
+    let _done = Done;
+    f()
+}
+
+/// Writes a result line to the file given by cargo-aoc, which checks the answers with it, leaving
+/// the output untouched
+#[allow(dead_code)]
+fn report(line: String) {
+    use std::io::Write;
+
+    if let Some(path) = std::env::var_os("CARGO_AOC_RESULTS") {
+        if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+/// Tells if an implementation already ran, when the runner is started again after a timeout
+#[allow(dead_code)]
+fn already_ran(key: &str) -> bool {
+    std::env::var("CARGO_AOC_DONE").is_ok_and(|done| done.lines().any(|k| k == key))
+}
+
+/// The stack size of the threads running the solutions, set with `--stack-size`
+const STACK_SIZE: Option<usize> = {STACK_SIZE};
+
+/// Builds the threads running the solutions, with the stack size set with `--stack-size`
+fn thread_builder() -> std::thread::Builder {
+    match STACK_SIZE {
+        Some(size) => std::thread::Builder::new().stack_size(size),
+        None => std::thread::Builder::new(),
+    }
+}
+
+{MEMORY}
+
+{PROGRESS}
+
 fn main() {
-    println!("AOC {YEAR}");
+    start_spinner();
+
+    // The stack size can only be chosen for new threads
+    if STACK_SIZE.is_some() {
+        let runner = thread_builder().spawn(run).expect("failed to spawn the runner thread");
+        if let Err(panic) = runner.join() {
+            panic::resume_unwind(panic);
+        }
+    } else {
+        run();
+    }
+}
+
+fn run() {
+    {HEADER}
 
     {INPUT}
 
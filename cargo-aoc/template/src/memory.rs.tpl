@@ -0,0 +1,79 @@
+/// Counts the allocations of the solutions, enabled with `--memory`
+#[allow(dead_code)]
+mod memory {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    /// The memory used when the solution started
+    static BASE: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counting;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    /// Counts an allocation of `size` bytes, replacing `freed` bytes for a reallocation
+    fn record(size: usize, freed: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED.fetch_add(size, Relaxed);
+        if size >= freed {
+            let current = CURRENT.fetch_add(size - freed, Relaxed) + size - freed;
+            PEAK.fetch_max(current, Relaxed);
+        } else {
+            CURRENT.fetch_sub(freed - size, Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record(new_size, layout.size());
+            }
+            new_ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+        }
+    }
+
+    /// Starts counting the allocations of a solution, its peak heap being measured above the heap
+    /// already used (like the inputs)
+    pub fn reset() {
+        let current = CURRENT.load(Relaxed);
+        ALLOCATIONS.store(0, Relaxed);
+        ALLOCATED.store(0, Relaxed);
+        BASE.store(current, Relaxed);
+        PEAK.store(current, Relaxed);
+    }
+
+    /// Gets the fields added to the result line since the last reset : the number of allocations,
+    /// the bytes allocated, and the peak of heap memory used
+    pub fn fields() -> String {
+        let (allocations, allocated) = (ALLOCATIONS.load(Relaxed), ALLOCATED.load(Relaxed));
+        let peak_heap = PEAK.load(Relaxed).saturating_sub(BASE.load(Relaxed));
+        format!("\t{}\t{}\t{}", allocations, allocated, peak_heap)
+    }
+}
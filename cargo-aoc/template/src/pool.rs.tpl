@@ -0,0 +1,17 @@
+
+    let tasks: Vec<Box<dyn FnOnce() + Send>> = vec![{TASKS}];
+    let tasks = std::sync::Mutex::new(tasks.into_iter());
+
+    std::thread::scope(|s| {
+        for _ in 0..{JOBS} {
+            thread_builder()
+                .spawn_scoped(s, || loop {
+                    let task = tasks.lock().unwrap().next();
+                    match task {
+                        Some(task) => task(),
+                        None => break,
+                    }
+                })
+                .expect("failed to spawn a worker thread");
+        }
+    });
\ No newline at end of file
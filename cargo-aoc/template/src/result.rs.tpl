@@ -0,0 +1,31 @@
+
+    {
+        let input_day{DAY} = input_day{DAY}.clone();
+        let run = move || {
+            memory::reset();
+            let start_time = Instant::now();
+
+            match panic::catch_unwind(|| with_spinner("{RUNNER_DISPLAY} - generator", || Factory::{RUNNER_NAME}(input_day{DAY}.clone()))) {
+                Ok(Ok(runner)) => {
+                    let inter_time = Instant::now();
+
+                    match panic::catch_unwind(AssertUnwindSafe(|| with_spinner("{RUNNER_DISPLAY}", || runner.try_run()))) {
+                        Ok(Ok(result)) => {
+                            let final_time = Instant::now();
+                            let memory = memory::fields();
+                            format!("\x1e{DAY}\t{PART}\t{NAME}\tok\t{}\t{}\t{}{}", (inter_time - start_time).as_nanos(), (final_time - inter_time).as_nanos(), result.to_string().escape_debug(), memory)
+                        },
+                        Ok(Err(e)) => format!("\x1e{DAY}\t{PART}\t{NAME}\trun_error\t{}\t0\t{}", (inter_time - start_time).as_nanos(), e.to_string().escape_debug()),
+                        Err(_) => format!("\x1e{DAY}\t{PART}\t{NAME}\tpanic\t{}\t0\tpanicked while running", (inter_time - start_time).as_nanos()),
+                    }
+                },
+                Ok(Err(e)) => format!("\x1e{DAY}\t{PART}\t{NAME}\tgen_error\t0\t0\t{}", e.to_string().escape_debug()),
+                Err(_) => format!("\x1e{DAY}\t{PART}\t{NAME}\tpanic\t0\t0\tpanicked while generating"),
+            }
+        };
+        if !already_ran("{DAY}/{PART}/{NAME}") {
+            for _ in 0..{RUNS} {
+{RUN}
+            }
+        }
+    }
\ No newline at end of file
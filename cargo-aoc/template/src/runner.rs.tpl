@@ -2,18 +2,25 @@
     {
         let start_time = Instant::now();
 
-        match Factory::{RUNNER_NAME}(input_day{DAY}.clone()) {
+        match with_spinner("{RUNNER_DISPLAY} - generator", || Factory::{RUNNER_NAME}(input_day{DAY}.clone())) {
             Ok(runner) => {
                 let inter_time = Instant::now();
 
-                match runner.try_run() {
+                match with_spinner("{RUNNER_DISPLAY}", || runner.try_run()) {
                     Ok(result) => {
                         let final_time = Instant::now();
-                        println!("{RUNNER_DISPLAY} : {}\n\tgenerator: {:?},\n\trunner: {:?}\n", result, (inter_time - start_time), (final_time - inter_time));
+                        report(format!("{DAY}\t{PART}\t{NAME}\tok\t{}\t{}\t{}", (inter_time - start_time).as_nanos(), (final_time - inter_time).as_nanos(), result.to_string().escape_debug()));
+                        println!("{RUNNER_DISPLAY} : {}\n\tgenerator: {},\n\trunner: {}\n", paint("1", &result), paint_time(inter_time - start_time), paint_time(final_time - inter_time));
                     },
-                    Err(e) => eprintln!("{RUNNER_DISPLAY} : FAILED while running :\n{:#?}\n", e)
+                    Err(e) => {
+                        report(format!("{DAY}\t{PART}\t{NAME}\trun_error\t{}\t0\t{}", (inter_time - start_time).as_nanos(), e.to_string().escape_debug()));
+                        eprintln!("{RUNNER_DISPLAY} : {} while running :\n{:#?}\n", paint("31", &"FAILED"), e)
+                    }
                 }
             },
-            Err(e) => eprintln!("{RUNNER_DISPLAY} : FAILED while generating :\n{:#?}\n", e)
+            Err(e) => {
+                report(format!("{DAY}\t{PART}\t{NAME}\tgen_error\t0\t0\t{}", e.to_string().escape_debug()));
+                eprintln!("{RUNNER_DISPLAY} : {} while generating :\n{:#?}\n", paint("31", &"FAILED"), e)
+            }
         }
     }
\ No newline at end of file
@@ -0,0 +1,16 @@
+            // The solution runs on its own thread. If it doesn't finish in time, the runner exits to
+            // stop it, and cargo-aoc starts a new one for the parts left
+            let run = run.clone();
+            let (sender, receiver) = std::sync::mpsc::channel();
+            thread_builder()
+                .spawn(move || {
+                    let _ = sender.send(run());
+                })
+                .expect("failed to spawn the solution thread");
+            match receiver.recv_timeout(Duration::from_nanos({TIMEOUT_NS})) {
+                Ok(line) => println!("{}", line),
+                Err(_) => {
+                    println!("\x1e{DAY}\t{PART}\t{NAME}\ttimeout\t0\t{TIMEOUT_NS}\ttimed out after {TIMEOUT}");
+                    std::process::exit(0);
+                }
+            }
\ No newline at end of file